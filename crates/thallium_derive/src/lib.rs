@@ -1,10 +1,12 @@
 #![doc = include_str!("../README.md")]
 
 use proc_macro::TokenStream;
-use quote::quote;
-use syn::{parse_macro_input, DeriveInput};
+use quote::{quote, ToTokens};
+use syn::{parse_macro_input, DeriveInput, Generics};
 
 /// Derives the [`Component`](thallium_ecs::Component) trait
+///
+/// If the type has no generic parameters and implements [`Clone`], [`Component::clone_fn`](thallium_ecs::Component::clone_fn) is implemented so that it can be cloned by [`App::clone_entity`](thallium_ecs::App::clone_entity)
 #[proc_macro_derive(Component)]
 pub fn derive_component(input: TokenStream) -> TokenStream {
     let input = parse_macro_input!(input as DeriveInput);
@@ -12,23 +14,73 @@ pub fn derive_component(input: TokenStream) -> TokenStream {
     let name = input.ident;
     let (impl_generics, ty_generics, where_clause) = input.generics.split_for_impl();
 
+    let clone_fn = clone_fn(&input.generics, quote!(clone_fn));
+
     quote! {
         impl #impl_generics ::thallium_ecs::Component for #name #ty_generics #where_clause {
+            #clone_fn
         }
     }
     .into()
 }
 
 /// Derives the [`Resource`](thallium_ecs::Resource) trait
+///
+/// If the type has no generic parameters and implements [`Clone`], [`Resource::resource_clone_fn`](thallium_ecs::Resource::resource_clone_fn) is implemented so that it can be cloned by [`App::try_clone`](thallium_ecs::App::try_clone)
 #[proc_macro_derive(Resource)]
 pub fn derive_resource(input: TokenStream) -> TokenStream {
     let input = parse_macro_input!(input as DeriveInput);
 
     let name = input.ident;
     let (impl_generics, ty_generics, where_clause) = input.generics.split_for_impl();
+    let clone_fn = clone_fn(&input.generics, quote!(resource_clone_fn));
 
     quote! {
         impl #impl_generics ::thallium_ecs::Resource for #name #ty_generics #where_clause {
+            #clone_fn
+        }
+    }
+    .into()
+}
+
+/// Implements the clone function `method` if the type implements [`Clone`], this can only be checked with auto-ref when the type has no generic parameters
+fn clone_fn(generics: &Generics, method: impl ToTokens) -> Option<impl ToTokens> {
+    generics.params.is_empty().then(|| {
+        quote! {
+            fn #method() -> ::core::option::Option<fn(&Self) -> Self> {
+                #[allow(unused_imports)]
+                use ::thallium_ecs::__private::{CloneFnFallback as _, CloneFnSpecialized as _};
+                (&&::thallium_ecs::__private::CloneFnProbe::<Self>(::core::marker::PhantomData)).clone_fn()
+            }
+        }
+    })
+}
+
+/// Derives the [`ScheduleLabel`](thallium_ecs::ScheduleLabel) trait
+#[proc_macro_derive(ScheduleLabel)]
+pub fn derive_schedule_label(input: TokenStream) -> TokenStream {
+    let input = parse_macro_input!(input as DeriveInput);
+
+    let name = input.ident;
+    let (impl_generics, ty_generics, where_clause) = input.generics.split_for_impl();
+
+    quote! {
+        impl #impl_generics ::thallium_ecs::ScheduleLabel for #name #ty_generics #where_clause {
+        }
+    }
+    .into()
+}
+
+/// Derives the [`States`](thallium_ecs::States) trait
+#[proc_macro_derive(States)]
+pub fn derive_states(input: TokenStream) -> TokenStream {
+    let input = parse_macro_input!(input as DeriveInput);
+
+    let name = input.ident;
+    let (impl_generics, ty_generics, where_clause) = input.generics.split_for_impl();
+
+    quote! {
+        impl #impl_generics ::thallium_ecs::States for #name #ty_generics #where_clause {
         }
     }
     .into()
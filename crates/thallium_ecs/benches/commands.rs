@@ -0,0 +1,72 @@
+//! Compares the command queue used by [`Commands`], which stores commands by value in a buffer per command type, against boxing every command and sending it through a channel
+#![allow(missing_docs)]
+
+use criterion::{criterion_group, criterion_main, BatchSize, Criterion};
+use std::sync::mpsc::channel;
+use thallium_ecs::{App, Commands, Component, SystemSet};
+
+struct Bullet {
+    _velocity: [f32; 2],
+}
+impl Component for Bullet {}
+
+const BULLETS: usize = 10_000;
+
+fn spawn(mut commands: Commands<'_>) {
+    for _ in 0..BULLETS {
+        commands.create_entity(Bullet {
+            _velocity: [1.0, 0.0],
+        });
+    }
+}
+
+fn spawn_bullets(c: &mut Criterion) {
+    let mut group = c.benchmark_group("spawn_bullets");
+
+    // a new system each time, so the buffers of the queue have to grow from empty
+    group.bench_function("command_queue", |b| {
+        b.iter_batched_ref(App::new, |app| app.run(spawn), BatchSize::SmallInput)
+    });
+
+    // the same system every time, so the buffers grown by the first run are reused like they are from tick to tick
+    let mut set = SystemSet::new();
+    set.register_system(spawn);
+    group.bench_function("command_queue_reused", |b| {
+        b.iter_batched_ref(App::new, |app| app.run(&mut set), BatchSize::SmallInput)
+    });
+
+    // the previous design, where every command was boxed and sent through a channel
+    group.bench_function("boxed_channel", |b| {
+        b.iter_batched_ref(
+            App::new,
+            |app| {
+                let (sender, receiver) = channel::<Box<dyn FnOnce(&mut App) + Send>>();
+                let sender = &sender;
+                app.run(move || {
+                    for _ in 0..BULLETS {
+                        sender
+                            .send(Box::new(|app: &mut App| {
+                                let entity = app.create_entity();
+                                app.add_component(
+                                    entity,
+                                    Bullet {
+                                        _velocity: [1.0, 0.0],
+                                    },
+                                );
+                            }))
+                            .unwrap();
+                    }
+                });
+                for command in receiver.try_iter() {
+                    command(app);
+                }
+            },
+            BatchSize::SmallInput,
+        )
+    });
+
+    group.finish();
+}
+
+criterion_group!(benches, spawn_bullets);
+criterion_main!(benches);
@@ -1,30 +1,146 @@
 use crate::{
+    command_queue::CommandQueue,
+    commands::{Scheduled, ScheduledCommandHandle},
     component_container::ComponentContainer,
     entities::EntityMap,
+    entity_clone::ResourceClone,
+    relationship::ComponentHooks,
     resource_container::ResourceContainer,
+    schedule::ScheduleKey,
+    state::RegisteredState,
     system::{ComponentMap, ResourceMap, SystemRunState},
-    Component, Entity, IntoSystem, Resource, System,
+    Clock, CloneError, Command, CommandError, CommandErrorHandler, CommandErrorKind, Commands,
+    Component, Entity, Executor, Fixed, FixedUpdate, IntoSystem, Parent, Real, RefMut, Resource,
+    ScheduleLabel, System, SystemClock, SystemDiagnostics, SystemHandle, SystemId, SystemKey,
+    SystemSet, Time, Update, Virtual,
+};
+use parking_lot::{Mutex, RwLock};
+use std::{
+    any::TypeId,
+    collections::{BTreeMap, HashMap},
+    sync::Arc,
+    time::Duration,
 };
-use parking_lot::RwLock;
-use std::{any::TypeId, collections::HashMap};
 
 /// The main struct that you will create for holding entities, components, and resources
 pub struct App {
-    resources: ResourceMap,
-    entities: EntityMap,
-    components: ComponentMap,
+    pub(crate) resources: ResourceMap,
+    pub(crate) resource_clones: HashMap<TypeId, ResourceClone>,
+    pub(crate) entities: EntityMap,
+    pub(crate) components: ComponentMap,
+    pub(crate) component_hooks: HashMap<TypeId, ComponentHooks>,
+    systems: HashMap<SystemId, Option<Box<dyn System<Input = (), Output = ()>>>>,
+    next_system_id: u64,
+    executor: Executor,
+    command_error_handler: CommandErrorHandler,
+    scheduled_commands: BTreeMap<u64, CommandQueue>,
+    handle_commands: Arc<Mutex<CommandQueue>>,
+    schedules: HashMap<ScheduleKey, Option<SystemSet<'static>>>,
+    clock: Box<dyn Clock>,
+    last_update: Option<Duration>,
+    pub(crate) state_transitions: Vec<RegisteredState>,
+    pub(crate) name_sync: Option<Box<dyn System<Input = (), Output = ()>>>,
     current_tick: u64,
 }
 
 impl App {
     /// Constructs an empty [`App`]
     pub fn new() -> Self {
-        Self {
+        let mut app = Self {
             resources: HashMap::new(),
+            resource_clones: HashMap::new(),
             entities: EntityMap::new(),
             components: HashMap::new(),
+            component_hooks: HashMap::new(),
+            systems: HashMap::new(),
+            next_system_id: 0,
+            executor: Executor::default(),
+            command_error_handler: CommandErrorHandler::default(),
+            scheduled_commands: BTreeMap::new(),
+            handle_commands: Arc::new(Mutex::new(CommandQueue::new())),
+            schedules: HashMap::new(),
+            clock: Box::new(SystemClock::new()),
+            last_update: None,
+            state_transitions: Vec::new(),
+            name_sync: None,
             current_tick: 0,
+        };
+        app.register_relationship::<Parent>();
+        app.register_names();
+        app.register_resource_clone::<Time<Real>>();
+        app.register_resource_clone::<Time<Virtual>>();
+        app.register_resource_clone::<Time<Fixed>>();
+        app.register_resource_clone::<Time>();
+        app.init_resource::<Time<Real>>();
+        app.init_resource::<Time<Virtual>>();
+        app.init_resource::<Time<Fixed>>();
+        app.init_resource::<Time>();
+        app
+    }
+
+    /// Deep copies the [`App`] so that it can be stepped independently, for example to simulate a few ticks ahead and then throw the copy away
+    ///
+    /// Every [`Entity`] keeps its id, and all [`Component`]s and [`Resource`]s are cloned along with the registered states and relationships,
+    /// but systems, schedules, scheduled commands, the [`Executor`] and the [`Clock`] are not, so any systems have to be added to the copy again
+    /// This returns a [`CloneError`] naming the first [`Component`] or [`Resource`] that cannot be cloned
+    pub fn try_clone(&self) -> Result<App, CloneError> {
+        let mut components = HashMap::new();
+        let mut not_cloneable: Option<(Entity, &'static str)> = None;
+        for (&component, container) in &self.components {
+            let container = container.read();
+            match container.try_clone() {
+                Some(clone) => _ = components.insert(component, RwLock::new(clone)),
+                // empty containers are left out of the copy, they will be created again when needed
+                None => {
+                    if let Some(entity) = container.first_entity() {
+                        if not_cloneable.is_none_or(|(first, _)| entity.id < first.id) {
+                            not_cloneable = Some((entity, container.type_name()));
+                        }
+                    }
+                }
+            }
+        }
+        if let Some((entity, component)) = not_cloneable {
+            return Err(CloneError::NotCloneable {
+                entity,
+                name: self.entity_name(entity),
+                component,
+            });
+        }
+
+        let mut resources: ResourceMap = HashMap::new();
+        let mut not_cloneable: Option<&'static str> = None;
+        for (&resource_type, resource) in &self.resources {
+            let resource = resource.read();
+            match self
+                .resource_clones
+                .get(&resource_type)
+                .and_then(|resource_clone| resource_clone.clone)
+            {
+                Some(clone) => _ = resources.insert(resource_type, RwLock::new(clone(&**resource))),
+                None => {
+                    let type_name = resource.type_name();
+                    if not_cloneable.is_none_or(|first| type_name < first) {
+                        not_cloneable = Some(type_name);
+                    }
+                }
+            }
+        }
+        if let Some(resource) = not_cloneable {
+            return Err(CloneError::ResourceNotCloneable { resource });
         }
+
+        let mut app = App::new();
+        app.resources = resources;
+        app.resource_clones = self.resource_clones.clone();
+        app.entities = self.entities.clone();
+        app.components = components;
+        app.component_hooks = self.component_hooks.clone();
+        app.next_system_id = self.next_system_id;
+        app.command_error_handler = self.command_error_handler;
+        app.state_transitions = self.state_transitions.clone();
+        app.current_tick = self.current_tick;
+        Ok(app)
     }
 
     /// Adds a [`Resource`] to the [`App`], currently if you add the same type of [`Resource`] twice it will replace the previous one
@@ -32,6 +148,9 @@ impl App {
     where
         R: Resource,
     {
+        self.resource_clones
+            .entry(TypeId::of::<R>())
+            .or_insert_with(ResourceClone::new::<R>);
         self.resources.insert(
             TypeId::of::<R>(),
             RwLock::new(Box::new(ResourceContainer {
@@ -41,6 +160,24 @@ impl App {
         );
     }
 
+    /// Adds the [`Default`] value of a [`Resource`] to the [`App`] if it doesnt already exist
+    pub fn init_resource<R>(&mut self)
+    where
+        R: Resource + Default,
+    {
+        if !self.has_resource::<R>() {
+            self.add_resource(R::default());
+        }
+    }
+
+    /// Checks if a [`Resource`] exists
+    pub fn has_resource<R>(&self) -> bool
+    where
+        R: Resource,
+    {
+        self.resources.contains_key(&TypeId::of::<R>())
+    }
+
     /// Removes a [`Resource`] from the [`App`] and returns it
     pub fn remove_resource<R>(&mut self) -> Option<R>
     where
@@ -49,20 +186,72 @@ impl App {
         self.resources.remove(&TypeId::of::<R>()).map(|resource| {
             resource
                 .into_inner()
+                .into_any()
                 .downcast::<ResourceContainer<R>>()
                 .unwrap()
                 .resource
         })
     }
 
+    /// Gets a [`Resource`] without marking it as modified, this takes `&mut self` so that the lock does not need to be held
+    pub(crate) fn resource<R>(&mut self) -> Option<&R>
+    where
+        R: Resource,
+    {
+        Some(
+            &self
+                .resources
+                .get_mut(&TypeId::of::<R>())?
+                .get_mut()
+                .downcast_ref::<R>()
+                .resource,
+        )
+    }
+
+    pub(crate) fn resource_mut<R>(&mut self) -> Option<&mut R>
+    where
+        R: Resource,
+    {
+        let container = self
+            .resources
+            .get_mut(&TypeId::of::<R>())?
+            .get_mut()
+            .downcast_mut::<R>();
+        container.last_modified_tick = self.current_tick;
+        Some(&mut container.resource)
+    }
+
     /// Creates an [`Entity`]
     pub fn create_entity(&mut self) -> Entity {
-        self.entities.create_entity()
+        let entity = self.entities.create_entity();
+        #[cfg(feature = "tracing")]
+        tracing::trace!(entity = ?self.entity_label(entity), "created entity");
+        entity
     }
 
     /// Destroys an [`Entity`] along with all its attached [`Component`]s
+    ///
+    /// Any [`Relationship`](crate::Relationship)s to or from the [`Entity`] are cleaned up according to their [`RelationshipCleanup`](crate::RelationshipCleanup),
+    /// so it is removed from the [`Children`](crate::Children) of its parent and its children are left without a [`Parent`](crate::Parent), use [`App::despawn_recursive`] to destroy the children as well
     pub fn destroy_entity(&mut self, entity: Entity) {
-        for component in self.entities.destroy_entity(entity).into_iter().flatten() {
+        let Some(components) = self.entities.destroy_entity(entity) else {
+            return;
+        };
+        // the label is taken before the hooks run, while the entity still has all of its components
+        #[cfg(feature = "tracing")]
+        tracing::trace!(entity = ?self.entity_label(entity), "destroyed entity");
+
+        // the entity is destroyed before the hooks run so that any cleanup that leads back to it stops here
+        let hooks = components
+            .iter()
+            .filter_map(|component| self.component_hooks.get(component))
+            .map(|hooks| hooks.on_remove)
+            .collect::<Vec<_>>();
+        for on_remove in hooks {
+            on_remove(self, entity);
+        }
+
+        for component in components {
             self.components
                 .get_mut(&component)
                 .unwrap()
@@ -71,29 +260,88 @@ impl App {
         }
     }
 
+    /// Gets a [`Component`] without locking, this takes `&mut self` so that the lock does not need to be held
+    pub(crate) fn component<C>(&mut self, entity: Entity) -> Option<&C>
+    where
+        C: Component,
+    {
+        self.components
+            .get_mut(&TypeId::of::<C>())?
+            .get_mut()
+            .downcast_mut::<C>()
+            .get(self.current_tick, entity)
+            .map(|component| component.component)
+    }
+
+    /// Gets a [`Component`] mutably without locking, it is marked as modified if it is dereferenced mutably
+    pub(crate) fn component_mut<C>(&mut self, entity: Entity) -> Option<RefMut<'_, C>>
+    where
+        C: Component,
+    {
+        let container = self
+            .components
+            .get_mut(&TypeId::of::<C>())?
+            .get_mut()
+            .downcast_mut::<C>();
+        container.last_borrowed_mut_tick = self.current_tick + 1;
+        container.get_mut(self.current_tick, self.current_tick + 1, entity)
+    }
+
     /// Checks if an [`Entity`] exists
     pub fn entity_exists(&self, entity: Entity) -> bool {
         self.entities.entity_exists(entity)
     }
 
     /// Adds a [`Component`] to an [`Entity`], currently if you add the same [`Component`] twice it will replace the previous one
+    /// This reports a [`CommandError`] if the [`Entity`] does not exist, use [`App::insert_if_alive`] if that is expected
     pub fn add_component<C>(&mut self, entity: Entity, component: C)
+    where
+        C: Component,
+    {
+        if !self.insert_if_alive(entity, component) {
+            self.report_command_error(CommandError {
+                kind: CommandErrorKind::InsertComponents,
+                entity,
+                components: vec![std::any::type_name::<C>()],
+            });
+        }
+    }
+
+    /// Adds a [`Component`] to an [`Entity`] if it exists, returning whether it was added
+    pub fn insert_if_alive<C>(&mut self, entity: Entity, component: C) -> bool
     where
         C: Component,
     {
         if !self.entity_exists(entity) {
-            return;
+            return false;
         }
 
         let component_id = TypeId::of::<C>();
+        let hooks = self.component_hooks.get(&component_id).copied();
+        if let Some(hooks) = hooks {
+            if self.component::<C>(entity).is_some() {
+                (hooks.on_remove)(self, entity);
+            }
+        }
+
         self.components
             .entry(component_id)
             .or_insert_with(|| RwLock::new(Box::new(ComponentContainer::<C>::new())))
             .get_mut()
             .downcast_mut::<C>()
             .insert(self.current_tick + 1, entity, component);
+        #[cfg(feature = "tracing")]
+        tracing::trace!(
+            entity = ?self.entity_label(entity),
+            component = std::any::type_name::<C>(),
+            "added component"
+        );
 
         self.entities.add_component(entity, component_id);
+        if let Some(on_insert) = hooks.and_then(|hooks| hooks.on_insert) {
+            on_insert(self, entity);
+        }
+        true
     }
 
     /// Removes a [`Component`] from an [`Entity`] and returns it
@@ -105,38 +353,356 @@ impl App {
             return None;
         }
 
+        if let Some(hooks) = self.component_hooks.get(&TypeId::of::<C>()).copied() {
+            if self.component::<C>(entity).is_some() {
+                (hooks.on_remove)(self, entity);
+            }
+        }
+        self.take_component(entity)
+    }
+
+    /// Removes a [`Component`] from an [`Entity`] without running any hooks, this also works while the [`Entity`] is being destroyed
+    pub(crate) fn take_component<C>(&mut self, entity: Entity) -> Option<C>
+    where
+        C: Component,
+    {
         let component_id = TypeId::of::<C>();
-        self.entities.remove_component(entity, component_id);
+        if self.entity_exists(entity) {
+            self.entities.remove_component(entity, component_id);
+        }
 
-        self.components
+        let component = self
+            .components
             .get_mut(&component_id)?
             .get_mut()
             .downcast_mut::<C>()
-            .remove(entity)
+            .remove(entity);
+        #[cfg(feature = "tracing")]
+        if component.is_some() {
+            tracing::trace!(
+                entity = ?self.entity_label(entity),
+                component = std::any::type_name::<C>(),
+                "removed component"
+            );
+        }
+        component
+    }
+
+    /// Runs a system with access to the [`App`], returning the output of the system after all [`Commands`](crate::Commands) have been applied
+    pub fn run<S, Marker>(&mut self, system: S) -> <S::System as System>::Output
+    where
+        S: IntoSystem<Marker>,
+        S::System: System<Input = ()>,
+    {
+        self.run_with_input(system, ())
+    }
+
+    /// Runs a system with access to the [`App`], passing `input` to its [`In`](crate::In) parameter and returning the output of the system after all [`Commands`](crate::Commands) have been applied
+    pub fn run_with_input<S, Marker>(
+        &mut self,
+        system: S,
+        input: <S::System as System>::Input,
+    ) -> <S::System as System>::Output
+    where
+        S: IntoSystem<Marker>,
+    {
+        self.run_system_mut(None, &mut system.into_system(), input)
     }
 
-    /// Runs a system with access to the [`App`]
-    pub fn run<S, Marker>(&mut self, system: S)
+    /// Registers a [`System`] with the [`App`] so that it can be run later with [`App::run_system`]
+    ///
+    /// The [`System`] keeps its state between runs, so modification checking works the same as for any other [`System`]
+    pub fn register_system<S, Marker>(&mut self, system: S) -> SystemId
     where
         S: IntoSystem<Marker>,
+        S::System: System<Input = (), Output = ()> + 'static,
     {
-        let (command_sender, command_receiver) = std::sync::mpsc::channel();
-        system.into_system().run(&SystemRunState {
-            resources: &self.resources,
-            entities: &self.entities,
-            components: &self.components,
-            command_sender: &command_sender,
-            current_tick: self.current_tick,
+        let id = SystemId(self.next_system_id);
+        self.next_system_id += 1;
+        self.systems
+            .insert(id, Some(Box::new(system.into_system())));
+        id
+    }
+
+    /// Unregisters a [`System`] that was registered with [`App::register_system`], returns `false` if there was no [`System`] registered with `id`
+    pub fn unregister_system(&mut self, id: SystemId) -> bool {
+        self.systems.remove(&id).is_some()
+    }
+
+    /// Runs a [`System`] that was registered with [`App::register_system`]
+    ///
+    /// Returns `false` if there is no [`System`] registered with `id`, or if that [`System`] is already running
+    pub fn run_system(&mut self, id: SystemId) -> bool {
+        let Some(mut system) = self.systems.get_mut(&id).and_then(Option::take) else {
+            return false;
+        };
+        self.run_system_mut(Some(SystemKey::Id(id)), &mut *system, ());
+        // the system may have been unregistered while it was running
+        if let Some(slot) = self.systems.get_mut(&id) {
+            *slot = Some(system);
+        }
+        true
+    }
+
+    /// Registers a [`System`] in the schedule named by `label`, creating the schedule if it does not exist yet
+    ///
+    /// # Panics
+    /// Panics if the schedule is currently running
+    pub fn add_system<L, S, Marker>(&mut self, label: L, system: S) -> SystemHandle
+    where
+        L: ScheduleLabel,
+        S: IntoSystem<Marker>,
+        S::System: System<Input = (), Output = ()> + 'static,
+    {
+        self.schedules
+            .entry(ScheduleKey::new(label.clone()))
+            .or_insert_with(|| Some(SystemSet::new()))
+            .as_mut()
+            .unwrap_or_else(|| panic!("cannot add a system to {label:?} while it is running"))
+            .register_system(system)
+    }
+
+    /// Gets the [`SystemSet`] of the schedule named by `label`, returns [`None`] if the schedule does not exist or is currently running
+    pub fn schedule_mut<L>(&mut self, label: L) -> Option<&mut SystemSet<'static>>
+    where
+        L: ScheduleLabel,
+    {
+        self.schedules.get_mut(&ScheduleKey::new(label))?.as_mut()
+    }
+
+    /// Runs the schedule named by `label`
+    ///
+    /// Returns `false` if the schedule does not exist, or if it is already running
+    pub fn run_schedule<L>(&mut self, label: L) -> bool
+    where
+        L: ScheduleLabel,
+    {
+        #[cfg(feature = "tracing")]
+        let _span = tracing::info_span!("schedule", ?label).entered();
+        let key = ScheduleKey::new(label);
+        let Some(mut schedule) = self.schedules.get_mut(&key).and_then(Option::take) else {
+            return false;
+        };
+        self.run(&mut schedule);
+        if let Some(slot) = self.schedules.get_mut(&key) {
+            *slot = Some(schedule);
+        }
+        true
+    }
+
+    /// Sets the [`Clock`] that [`App::update`] reads the time from, the default is a [`SystemClock`]
+    pub fn set_clock<C>(&mut self, clock: C)
+    where
+        C: Clock,
+    {
+        self.clock = Box::new(clock);
+        self.last_update = None;
+    }
+
+    /// Runs a single frame
+    ///
+    /// This advances the [`Time`] [`Resource`]s, applies any changes queued with [`NextState`](crate::NextState), runs [`FixedUpdate`] once for every step of `Time<Fixed>` that has passed, then runs [`Update`], then calls [`App::next_tick`]
+    pub fn update(&mut self) {
+        let now = self.clock.now();
+        let delta = self.last_update.map_or(Duration::ZERO, |last_update| {
+            now.saturating_sub(last_update)
         });
-        drop(command_sender);
-        for command in command_receiver {
-            command(self);
+        self.last_update = Some(now);
+
+        if let Some(real_time) = self.resource_mut::<Time<Real>>() {
+            real_time.advance_by(delta);
+        }
+        let virtual_time = self
+            .resource_mut::<Time<Virtual>>()
+            .map(|virtual_time| {
+                virtual_time.advance_virtual(delta);
+                virtual_time.as_generic()
+            })
+            .unwrap_or_default();
+        if let Some(fixed_time) = self.resource_mut::<Time<Fixed>>() {
+            fixed_time.accumulate(virtual_time.delta());
+        }
+
+        self.apply_state_transitions();
+
+        while let Some(fixed_time) = self
+            .resource_mut::<Time<Fixed>>()
+            .and_then(|fixed_time| fixed_time.expend().then(|| fixed_time.as_generic()))
+        {
+            if let Some(time) = self.resource_mut::<Time>() {
+                *time = fixed_time;
+            }
+            self.run_schedule(FixedUpdate);
+        }
+
+        if let Some(time) = self.resource_mut::<Time>() {
+            *time = virtual_time;
+        }
+        self.run_schedule(Update);
+        self.next_tick();
+    }
+
+    fn run_system_mut<S>(
+        &mut self,
+        key: Option<SystemKey>,
+        system: &mut S,
+        input: S::Input,
+    ) -> S::Output
+    where
+        S: System + ?Sized,
+    {
+        let mut commands = Mutex::new(CommandQueue::new());
+        let diagnostics = self
+            .resources
+            .contains_key(&TypeId::of::<SystemDiagnostics>())
+            .then(|| Mutex::new(Vec::new()));
+        let output = system.run(
+            input,
+            &SystemRunState {
+                resources: &self.resources,
+                entities: &self.entities,
+                components: &self.components,
+                commands: &commands,
+                diagnostics: diagnostics.as_ref(),
+                system: key,
+                executor: &self.executor,
+                current_tick: self.current_tick,
+            },
+        );
+        if let Some(diagnostics) = diagnostics {
+            if let Some(resource) = self.resources.get_mut(&TypeId::of::<SystemDiagnostics>()) {
+                let container = resource.get_mut().downcast_mut::<SystemDiagnostics>();
+                container.resource.record(diagnostics.into_inner());
+                container.last_modified_tick = self.current_tick;
+            }
+        }
+        {
+            #[cfg(feature = "tracing")]
+            let _span = tracing::info_span!("apply_commands").entered();
+            system.apply_commands(self);
+            commands.get_mut().apply(self);
+        }
+        output
+    }
+
+    /// Sets the [`Executor`] that is used to run [`SystemSet`](crate::SystemSet)s
+    pub fn set_executor(&mut self, executor: Executor) {
+        self.executor = executor;
+    }
+
+    /// Gets the [`Executor`] that is used to run [`SystemSet`](crate::SystemSet)s
+    pub fn executor(&self) -> &Executor {
+        &self.executor
+    }
+
+    /// Sets the [`CommandErrorHandler`] that decides what happens when a command fails
+    pub fn set_command_error_handler(&mut self, handler: CommandErrorHandler) {
+        self.command_error_handler = handler;
+    }
+
+    /// Gets the [`CommandErrorHandler`] that decides what happens when a command fails
+    pub fn command_error_handler(&self) -> CommandErrorHandler {
+        self.command_error_handler
+    }
+
+    /// Passes a [`CommandError`] to the [`CommandErrorHandler`], this is useful for reporting failures from custom [`Command`](crate::Command)s
+    /// The [`Entity`] is labelled with its [`Name`](crate::Name) from [`Names`](crate::Names), this includes destroyed [`Entity`]s
+    pub fn report_command_error(&self, error: CommandError) {
+        let label = self.entity_label(error.entity);
+        self.command_error_handler.handle(&error, &label);
+    }
+
+    /// Schedules a [`Command`] to be applied by [`App::next_tick`] once the current tick reaches `tick`
+    ///
+    /// Commands that are due on the same tick are applied in the order they were scheduled, if `tick` has already passed the command is applied by the next call to [`App::next_tick`]
+    pub fn schedule_at<C>(&mut self, tick: u64, command: C) -> ScheduledCommandHandle
+    where
+        C: Command,
+    {
+        let handle = ScheduledCommandHandle::default();
+        self.push_scheduled(
+            tick,
+            Scheduled {
+                command,
+                handle: handle.clone(),
+            },
+        );
+        handle
+    }
+
+    /// Schedules a [`Command`] to be applied by [`App::next_tick`] once `ticks` more ticks have passed
+    pub fn schedule_after<C>(&mut self, ticks: u64, command: C) -> ScheduledCommandHandle
+    where
+        C: Command,
+    {
+        self.schedule_at(self.current_tick + ticks, command)
+    }
+
+    pub(crate) fn push_scheduled<C>(&mut self, tick: u64, scheduled: Scheduled<C>)
+    where
+        C: Command,
+    {
+        self.scheduled_commands
+            .entry(tick)
+            .or_insert_with(CommandQueue::new)
+            .push(scheduled);
+    }
+
+    /// Gets the current tick, see [`App::next_tick`]
+    pub fn current_tick(&self) -> u64 {
+        self.current_tick
+    }
+
+    /// Returns an [`AppHandle`] that can be used to queue commands from other threads
+    pub fn handle(&self) -> AppHandle {
+        AppHandle {
+            commands: self.handle_commands.clone(),
         }
     }
 
     /// Advances to the next tick, this effects stuff like modification checking
+    ///
+    /// [`Names`](crate::Names) is first synced with any [`Name`](crate::Name)s that were changed in place,
+    /// then any commands queued with an [`AppHandle`] are applied, then any commands scheduled with [`App::schedule_at`] that are now due
     pub fn next_tick(&mut self) {
+        if let Some(mut name_sync) = self.name_sync.take() {
+            self.run_system_mut(None, &mut *name_sync, ());
+            self.name_sync = Some(name_sync);
+        }
+
+        let mut handle_commands =
+            std::mem::replace(&mut *self.handle_commands.lock(), CommandQueue::new());
+        {
+            #[cfg(feature = "tracing")]
+            let _span = tracing::info_span!("handle_commands").entered();
+            handle_commands.apply(self);
+        }
+
         self.current_tick += 1;
+
+        #[cfg(feature = "tracing")]
+        let _span = tracing::info_span!("scheduled_commands").entered();
+        while let Some(entry) = self.scheduled_commands.first_entry() {
+            if *entry.key() > self.current_tick {
+                break;
+            }
+            entry.remove().apply(self);
+        }
+    }
+}
+
+/// A handle for queueing commands into an [`App`] from any thread, created with [`App::handle`]
+///
+/// The commands are applied at the start of the next call to [`App::next_tick`], in the order they were queued
+#[derive(Clone)]
+pub struct AppHandle {
+    commands: Arc<Mutex<CommandQueue>>,
+}
+
+impl AppHandle {
+    /// Returns [`Commands`] that queue into the [`App`] this handle was created from
+    pub fn commands(&self) -> Commands<'_> {
+        Commands::new(&self.commands)
     }
 }
 
@@ -0,0 +1,121 @@
+use crate::{Entity, EntityLabel};
+use std::fmt;
+
+/// The kind of command that produced a [`CommandError`]
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Hash)]
+pub enum CommandErrorKind {
+    /// Adding [`Component`](crate::Component)s to an [`Entity`]
+    InsertComponents,
+    /// Removing [`Component`](crate::Component)s from an [`Entity`]
+    RemoveComponents,
+    /// Destroying an [`Entity`]
+    DestroyEntity,
+    /// Setting the parent of an [`Entity`], or spawning a child under it
+    SetParent,
+    /// Adding a [`Relationship`](crate::Relationship) between two [`Entity`]s
+    Relate,
+    /// Cloning an [`Entity`]
+    CloneEntity,
+}
+
+impl fmt::Display for CommandErrorKind {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        f.write_str(match self {
+            CommandErrorKind::InsertComponents => "insert components",
+            CommandErrorKind::RemoveComponents => "remove components",
+            CommandErrorKind::DestroyEntity => "destroy entity",
+            CommandErrorKind::SetParent => "set parent",
+            CommandErrorKind::Relate => "relate",
+            CommandErrorKind::CloneEntity => "clone entity",
+        })
+    }
+}
+
+/// A command that was applied to an [`Entity`] that does not exist, passed to the [`CommandErrorHandler`] of the [`App`](crate::App)
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub struct CommandError {
+    /// The kind of command that failed
+    pub kind: CommandErrorKind,
+    /// The [`Entity`] that the command was applied to
+    pub entity: Entity,
+    /// The [`std::any::type_name`]s of the [`Component`](crate::Component)s that the command was adding or removing
+    pub components: Vec<&'static str>,
+}
+
+impl CommandError {
+    /// Returns a [`Display`](fmt::Display) for the error that prints the [`Entity`] as `label`, which is passed to the [`CommandErrorHandler`] along with the error
+    pub fn with_label<'a>(&'a self, label: &'a EntityLabel) -> impl fmt::Display + 'a {
+        LabelledCommandError { error: self, label }
+    }
+}
+
+impl fmt::Display for CommandError {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        let label = EntityLabel {
+            entity: self.entity,
+            name: None,
+        };
+        LabelledCommandError {
+            error: self,
+            label: &label,
+        }
+        .fmt(f)
+    }
+}
+
+struct LabelledCommandError<'a> {
+    error: &'a CommandError,
+    label: &'a EntityLabel,
+}
+
+impl fmt::Display for LabelledCommandError<'_> {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        write!(f, "{} command failed for {}", self.error.kind, self.label)?;
+        if !self.error.components.is_empty() {
+            write!(f, " with components [{}]", self.error.components.join(", "))?;
+        }
+        write!(f, ", the entity does not exist")
+    }
+}
+
+impl std::error::Error for CommandError {}
+
+/// Decides what happens when a command fails, set with [`App::set_command_error_handler`](crate::App::set_command_error_handler)
+#[derive(Debug, Clone, Copy)]
+pub enum CommandErrorHandler {
+    /// Silently ignores the error
+    Ignore,
+    /// Prints the error to stderr, or logs it with [`tracing`](https://docs.rs/tracing) if the `tracing` feature is enabled
+    Warn,
+    /// Panics with the error
+    Panic,
+    /// Calls the function with the error and an [`EntityLabel`] for the [`Entity`] it was applied to
+    Custom(fn(&CommandError, &EntityLabel)),
+}
+
+impl CommandErrorHandler {
+    pub(crate) fn handle(&self, error: &CommandError, label: &EntityLabel) {
+        match self {
+            CommandErrorHandler::Ignore => {}
+            CommandErrorHandler::Warn => {
+                #[cfg(feature = "tracing")]
+                tracing::warn!("{}", error.with_label(label));
+                #[cfg(not(feature = "tracing"))]
+                eprintln!("warning: {}", error.with_label(label));
+            }
+            CommandErrorHandler::Panic => panic!("{}", error.with_label(label)),
+            CommandErrorHandler::Custom(f) => f(error, label),
+        }
+    }
+}
+
+impl Default for CommandErrorHandler {
+    /// Returns [`CommandErrorHandler::Warn`] in debug builds, otherwise [`CommandErrorHandler::Ignore`]
+    fn default() -> Self {
+        if cfg!(debug_assertions) {
+            CommandErrorHandler::Warn
+        } else {
+            CommandErrorHandler::Ignore
+        }
+    }
+}
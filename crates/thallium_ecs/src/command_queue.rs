@@ -0,0 +1,101 @@
+use crate::{App, Command};
+use std::{
+    any::{Any, TypeId},
+    collections::{HashMap, VecDeque},
+};
+
+/// The queued commands of a single type, in the order they were pushed
+trait CommandBuffer: Send {
+    fn as_any_mut(&mut self) -> &mut dyn Any;
+    fn apply_front(&mut self, count: usize, app: &mut App);
+    fn clear(&mut self);
+}
+
+impl<C> CommandBuffer for VecDeque<C>
+where
+    C: Command,
+{
+    fn as_any_mut(&mut self) -> &mut dyn Any {
+        self
+    }
+
+    fn apply_front(&mut self, count: usize, app: &mut App) {
+        for command in self.drain(..count) {
+            command.apply(app);
+        }
+    }
+
+    fn clear(&mut self) {
+        VecDeque::clear(self);
+    }
+}
+
+/// A queue of commands, each [`System`](crate::System) has its own so that commands are always applied in the same order
+///
+/// Commands are stored by value in one buffer per command type, along with the order that the buffers were pushed to as runs of commands of the same type,
+/// so once the buffers have grown queueing a command does not allocate, and the buffers are kept when the queue is applied so they can be reused next tick
+pub struct CommandQueue {
+    buffers: Vec<(TypeId, Box<dyn CommandBuffer>)>,
+    buffer_indices: HashMap<TypeId, usize>,
+    /// The index of a buffer and the number of commands to take from it
+    order: Vec<(usize, usize)>,
+}
+
+impl CommandQueue {
+    pub(crate) fn new() -> Self {
+        Self {
+            buffers: Vec::new(),
+            buffer_indices: HashMap::new(),
+            order: Vec::new(),
+        }
+    }
+
+    pub(crate) fn push<C>(&mut self, command: C)
+    where
+        C: Command,
+    {
+        let type_id = TypeId::of::<C>();
+        let index = match self.order.last_mut() {
+            // commands of the same type are usually queued together, so this skips looking up the buffer
+            Some((index, count)) if self.buffers[*index].0 == type_id => {
+                *count += 1;
+                *index
+            }
+            _ => {
+                let index = *self.buffer_indices.entry(type_id).or_insert_with(|| {
+                    self.buffers.push((type_id, Box::new(VecDeque::<C>::new())));
+                    self.buffers.len() - 1
+                });
+                self.order.push((index, 1));
+                index
+            }
+        };
+        self.buffers[index]
+            .1
+            .as_any_mut()
+            .downcast_mut::<VecDeque<C>>()
+            .unwrap()
+            .push_back(command);
+    }
+
+    /// Applies all commands in the order they were pushed, leaving the queue empty but keeping its allocations
+    pub(crate) fn apply(&mut self, app: &mut App) {
+        let queue = ClearOnDrop(self);
+        let CommandQueue { buffers, order, .. } = &mut *queue.0;
+        for &(index, count) in order.iter() {
+            buffers[index].1.apply_front(count, app);
+        }
+    }
+}
+
+/// Empties the queue once it has been applied, if a command panics this drops the commands after it so that the order stays in step with the buffers
+struct ClearOnDrop<'a>(&'a mut CommandQueue);
+
+impl Drop for ClearOnDrop<'_> {
+    fn drop(&mut self) {
+        self.0.order.clear();
+        for (_, buffer) in &mut self.0.buffers {
+            buffer.clear();
+        }
+    }
+}
@@ -1,79 +1,460 @@
 use crate::{
-    component::ComponentBundle,
-    system::{CommandSender, SystemRunState},
-    App, Entity, SystemParameter,
+    command_queue::CommandQueue, component::ComponentBundle, system::SystemRunState, App,
+    CommandError, CommandErrorKind, Entity, Resource, SystemId, SystemParameter,
 };
+use parking_lot::Mutex;
+use std::{
+    marker::PhantomData,
+    sync::{
+        atomic::{AtomicBool, Ordering},
+        Arc,
+    },
+};
+
+/// A command that can be queued with [`Commands::add`] to be applied to the [`App`] later
+///
+/// This is implemented for all closures that take a `&mut App`
+pub trait Command: Send + 'static {
+    /// Applies this command to the [`App`]
+    fn apply(self, app: &mut App);
+}
+
+impl<F> Command for F
+where
+    F: FnOnce(&mut App) + Send + 'static,
+{
+    fn apply(self, app: &mut App) {
+        self(app);
+    }
+}
+
+pub(crate) struct CreateEntity<B>(pub(crate) B);
+
+impl<B> Command for CreateEntity<B>
+where
+    B: ComponentBundle,
+{
+    fn apply(self, app: &mut App) {
+        let entity = app.create_entity();
+        self.0.add(app, entity);
+    }
+}
+
+pub(crate) struct DestroyEntity {
+    pub(crate) entity: Entity,
+    pub(crate) report: bool,
+}
+
+impl Command for DestroyEntity {
+    fn apply(self, app: &mut App) {
+        if !app.entity_exists(self.entity) {
+            if self.report {
+                report::<()>(app, CommandErrorKind::DestroyEntity, self.entity);
+            }
+            return;
+        }
+        app.destroy_entity(self.entity);
+    }
+}
+
+pub(crate) struct RemoveComponents<B> {
+    pub(crate) entity: Entity,
+    pub(crate) report: bool,
+    pub(crate) _bundle: PhantomData<fn() -> B>,
+}
+
+impl<B> Command for RemoveComponents<B>
+where
+    B: ComponentBundle,
+{
+    fn apply(self, app: &mut App) {
+        if !app.entity_exists(self.entity) {
+            if self.report {
+                report::<B>(app, CommandErrorKind::RemoveComponents, self.entity);
+            }
+            return;
+        }
+        B::remove(app, self.entity);
+    }
+}
+
+pub(crate) struct InsertComponents<B> {
+    pub(crate) entity: Entity,
+    pub(crate) bundle: B,
+    pub(crate) report: bool,
+}
+
+impl<B> Command for InsertComponents<B>
+where
+    B: ComponentBundle,
+{
+    fn apply(self, app: &mut App) {
+        if !app.entity_exists(self.entity) {
+            if self.report {
+                report::<B>(app, CommandErrorKind::InsertComponents, self.entity);
+            }
+            return;
+        }
+        self.bundle.add(app, self.entity);
+    }
+}
+
+/// A handle to a command scheduled with [`Commands::schedule_at`] or [`App::schedule_at`], that can be used to cancel it
+#[derive(Debug, Clone, Default)]
+pub struct ScheduledCommandHandle {
+    cancelled: Arc<AtomicBool>,
+}
+
+impl ScheduledCommandHandle {
+    /// Cancels the command so that it is dropped instead of applied when it is due
+    /// This does nothing if the command has already been applied
+    pub fn cancel(&self) {
+        self.cancelled.store(true, Ordering::Relaxed);
+    }
+
+    /// Checks if the command has been cancelled
+    pub fn is_cancelled(&self) -> bool {
+        self.cancelled.load(Ordering::Relaxed)
+    }
+}
+
+pub(crate) struct Scheduled<C> {
+    pub(crate) command: C,
+    pub(crate) handle: ScheduledCommandHandle,
+}
+
+impl<C> Command for Scheduled<C>
+where
+    C: Command,
+{
+    fn apply(self, app: &mut App) {
+        if !self.handle.is_cancelled() {
+            self.command.apply(app);
+        }
+    }
+}
+
+fn report<B>(app: &App, kind: CommandErrorKind, entity: Entity)
+where
+    B: ComponentBundle,
+{
+    let mut components = Vec::new();
+    B::type_names(&mut components);
+    app.report_command_error(CommandError {
+        kind,
+        entity,
+        components,
+    });
+}
+
+pub(crate) struct InsertResource<R>(pub(crate) R);
+
+impl<R> Command for InsertResource<R>
+where
+    R: Resource,
+{
+    fn apply(self, app: &mut App) {
+        app.add_resource(self.0);
+    }
+}
+
+pub(crate) struct InitResource<R>(pub(crate) PhantomData<fn() -> R>);
+
+impl<R> Command for InitResource<R>
+where
+    R: Resource + Default,
+{
+    fn apply(self, app: &mut App) {
+        app.init_resource::<R>();
+    }
+}
+
+pub(crate) struct RemoveResource<R>(pub(crate) PhantomData<fn() -> R>);
+
+impl<R> Command for RemoveResource<R>
+where
+    R: Resource,
+{
+    fn apply(self, app: &mut App) {
+        app.remove_resource::<R>();
+    }
+}
+
+pub(crate) struct RunSystem(pub(crate) SystemId);
+
+impl Command for RunSystem {
+    fn apply(self, app: &mut App) {
+        app.run_system(self.0);
+    }
+}
 
 /// A [`SystemParameter`] that allows you to create/destroy [`Entity`]s, add/remove [`Component`](crate::Component)s, etc
+///
+/// Commands are applied after the [`System`](crate::System) has run, in the order that they were queued, commands from [`System`](crate::System)s in a [`SystemSet`](crate::SystemSet) are applied in the order the [`System`](crate::System)s were registered
 pub struct Commands<'a> {
-    command_sender: &'a CommandSender,
+    commands: &'a Mutex<CommandQueue>,
 }
 
-impl Commands<'_> {
+impl<'a> Commands<'a> {
+    pub(crate) fn new(commands: &'a Mutex<CommandQueue>) -> Self {
+        Self { commands }
+    }
+
+    /// Queues a [`Command`] to be applied to the [`App`]
+    pub fn add<C>(&mut self, command: C)
+    where
+        C: Command,
+    {
+        self.commands.lock().push(command);
+    }
+
+    /// Returns an [`EntityCommands`] for queueing commands that modify `entity`
+    pub fn entity(&mut self, entity: Entity) -> EntityCommands<'_, 'a> {
+        EntityCommands {
+            entity,
+            commands: self,
+        }
+    }
+
     /// Creates an [`Entity`]
     pub fn create_entity<B>(&mut self, bundle: B)
     where
         B: ComponentBundle,
     {
-        self.command_sender
-            .send(Box::new(move |app| {
-                let entity = app.create_entity();
-                bundle.add(app, entity);
-            }))
-            .unwrap();
+        self.add(CreateEntity(bundle));
     }
 
     /// Schedules an [`Entity`] to be destroyed along with all its attached components
-    /// This does not error if the [`Entity`] is already destroyed
+    /// This reports a [`CommandError`] if the [`Entity`] is already destroyed, use [`Commands::try_destroy_entity`] if that is expected
     pub fn destroy_entity(&mut self, entity: Entity) {
-        self.command_sender
-            .send(Box::new(move |app| app.destroy_entity(entity)))
-            .unwrap();
+        self.add(DestroyEntity {
+            entity,
+            report: true,
+        });
+    }
+
+    /// Schedules an [`Entity`] to be destroyed along with all its attached components
+    /// This does nothing if the [`Entity`] is already destroyed
+    pub fn try_destroy_entity(&mut self, entity: Entity) {
+        self.add(DestroyEntity {
+            entity,
+            report: false,
+        });
     }
 
     /// Adds a bundle of [`Component`](crate::Component)s to an [`Entity`], any [`Component`](crate::Component)s that are already attached will be replaced
-    /// This does not error if the [`Entity`] is invalid/destroyed
+    /// This reports a [`CommandError`] if the [`Entity`] is invalid/destroyed, use [`Commands::try_add_components`] if that is expected
     pub fn add_components<B>(&mut self, entity: Entity, bundle: B)
     where
         B: ComponentBundle,
     {
-        self.command_sender
-            .send(Box::new(move |app| bundle.add(app, entity)))
-            .unwrap();
+        self.add(InsertComponents {
+            entity,
+            bundle,
+            report: true,
+        });
+    }
+
+    /// Adds a bundle of [`Component`](crate::Component)s to an [`Entity`], any [`Component`](crate::Component)s that are already attached will be replaced
+    /// This does nothing if the [`Entity`] is invalid/destroyed
+    pub fn try_add_components<B>(&mut self, entity: Entity, bundle: B)
+    where
+        B: ComponentBundle,
+    {
+        self.add(InsertComponents {
+            entity,
+            bundle,
+            report: false,
+        });
     }
 
     /// Removes a bundle of [`Component`](crate::Component)s from an [`Entity`]
-    /// This does not error if the [`Entity`] is invalid/destroyed or any of the [`Component`](crate::Component)s are not attached to this [`Entity`]
+    /// This reports a [`CommandError`] if the [`Entity`] is invalid/destroyed, use [`Commands::try_remove_components`] if that is expected, it does not error if any of the [`Component`](crate::Component)s are not attached to this [`Entity`]
     pub fn remove_components<B>(&mut self, entity: Entity)
     where
         B: ComponentBundle,
     {
-        self.command_sender
-            .send(Box::new(move |app| B::remove(app, entity)))
-            .unwrap();
+        self.add(RemoveComponents::<B> {
+            entity,
+            report: true,
+            _bundle: PhantomData,
+        });
+    }
+
+    /// Removes a bundle of [`Component`](crate::Component)s from an [`Entity`]
+    /// This does not error if the [`Entity`] is invalid/destroyed or any of the [`Component`](crate::Component)s are not attached to this [`Entity`]
+    pub fn try_remove_components<B>(&mut self, entity: Entity)
+    where
+        B: ComponentBundle,
+    {
+        self.add(RemoveComponents::<B> {
+            entity,
+            report: false,
+            _bundle: PhantomData,
+        });
+    }
+
+    /// Adds a [`Resource`] to the [`App`], replacing the previous one if it already exists
+    pub fn insert_resource<R>(&mut self, resource: R)
+    where
+        R: Resource,
+    {
+        self.add(InsertResource(resource));
+    }
+
+    /// Adds the [`Default`] value of a [`Resource`] to the [`App`] if it doesnt already exist
+    pub fn init_resource<R>(&mut self)
+    where
+        R: Resource + Default,
+    {
+        self.add(InitResource::<R>(PhantomData));
+    }
+
+    /// Removes a [`Resource`] from the [`App`]
+    /// This does not error if the [`Resource`] does not exist
+    pub fn remove_resource<R>(&mut self)
+    where
+        R: Resource,
+    {
+        self.add(RemoveResource::<R>(PhantomData));
+    }
+
+    /// Schedules a [`System`](crate::System) registered with [`App::register_system`] to be run
+    /// This does not error if the [`System`](crate::System) has been unregistered
+    pub fn run_system(&mut self, id: SystemId) {
+        self.add(RunSystem(id));
     }
 
     /// Schedules an arbitrary closure to be run after the current [`SystemSet`](crate::SystemSet) has finished
     pub fn schedule(&mut self, f: impl FnOnce(&mut App) + Send + 'static) {
-        self.command_sender
-            .send(Box::new(move |app| f(app)))
-            .unwrap();
+        self.add(f);
+    }
+
+    /// Schedules a [`Command`] to be applied by [`App::next_tick`] once the current tick reaches `tick`, see [`App::schedule_at`]
+    pub fn schedule_at<C>(&mut self, tick: u64, command: C) -> ScheduledCommandHandle
+    where
+        C: Command,
+    {
+        let handle = ScheduledCommandHandle::default();
+        let scheduled = Scheduled {
+            command,
+            handle: handle.clone(),
+        };
+        self.add(move |app: &mut App| app.push_scheduled(tick, scheduled));
+        handle
+    }
+
+    /// Schedules a [`Command`] to be applied by [`App::next_tick`] once `ticks` more ticks have passed, see [`App::schedule_after`]
+    pub fn schedule_after<C>(&mut self, ticks: u64, command: C) -> ScheduledCommandHandle
+    where
+        C: Command,
+    {
+        let handle = ScheduledCommandHandle::default();
+        let scheduled = Scheduled {
+            command,
+            handle: handle.clone(),
+        };
+        self.add(move |app: &mut App| app.push_scheduled(app.current_tick() + ticks, scheduled));
+        handle
+    }
+}
+
+/// Queues commands that modify a single [`Entity`], created with [`Commands::entity`]
+pub struct EntityCommands<'a, 'b> {
+    entity: Entity,
+    commands: &'a mut Commands<'b>,
+}
+
+impl<'b> EntityCommands<'_, 'b> {
+    /// Returns the [`Entity`] that these commands modify
+    pub fn id(&self) -> Entity {
+        self.entity
+    }
+
+    /// Adds a bundle of [`Component`](crate::Component)s to the [`Entity`], any [`Component`](crate::Component)s that are already attached will be replaced
+    /// This reports a [`CommandError`] if the [`Entity`] has been destroyed, use [`EntityCommands::try_insert`] if that is expected
+    pub fn insert<B>(&mut self, bundle: B) -> &mut Self
+    where
+        B: ComponentBundle,
+    {
+        self.commands.add_components(self.entity, bundle);
+        self
+    }
+
+    /// Adds a bundle of [`Component`](crate::Component)s to the [`Entity`], any [`Component`](crate::Component)s that are already attached will be replaced
+    /// This does nothing if the [`Entity`] has been destroyed
+    pub fn try_insert<B>(&mut self, bundle: B) -> &mut Self
+    where
+        B: ComponentBundle,
+    {
+        self.commands.try_add_components(self.entity, bundle);
+        self
+    }
+
+    /// Removes a bundle of [`Component`](crate::Component)s from the [`Entity`]
+    /// This reports a [`CommandError`] if the [`Entity`] has been destroyed, use [`EntityCommands::try_remove`] if that is expected
+    pub fn remove<B>(&mut self) -> &mut Self
+    where
+        B: ComponentBundle,
+    {
+        self.commands.remove_components::<B>(self.entity);
+        self
+    }
+
+    /// Removes a bundle of [`Component`](crate::Component)s from the [`Entity`]
+    /// This does not error if the [`Entity`] has been destroyed or any of the [`Component`](crate::Component)s are not attached to it
+    pub fn try_remove<B>(&mut self) -> &mut Self
+    where
+        B: ComponentBundle,
+    {
+        self.commands.try_remove_components::<B>(self.entity);
+        self
+    }
+
+    /// Destroys the [`Entity`] along with all its attached [`Component`](crate::Component)s
+    /// This reports a [`CommandError`] if the [`Entity`] is already destroyed, use [`EntityCommands::try_despawn`] if that is expected
+    pub fn despawn(&mut self) {
+        self.commands.destroy_entity(self.entity);
+    }
+
+    /// Destroys the [`Entity`] along with all its attached [`Component`](crate::Component)s
+    /// This does nothing if the [`Entity`] is already destroyed
+    pub fn try_despawn(&mut self) {
+        self.commands.try_destroy_entity(self.entity);
+    }
+
+    /// Schedules a closure to be run with the [`Entity`] by [`App::next_tick`] once `ticks` more ticks have passed
+    /// The closure is not run if the [`Entity`] has been destroyed by then
+    pub fn schedule_after(
+        &mut self,
+        ticks: u64,
+        f: impl FnOnce(&mut App, Entity) + Send + 'static,
+    ) -> ScheduledCommandHandle {
+        let entity = self.entity;
+        self.commands.schedule_after(ticks, move |app: &mut App| {
+            if app.entity_exists(entity) {
+                f(app, entity);
+            }
+        })
+    }
+
+    /// Returns the [`Commands`] that these commands are queued with
+    pub fn commands(&mut self) -> &mut Commands<'b> {
+        self.commands
     }
 }
 
 impl<'a> SystemParameter for Commands<'a> {
     type This<'this> = Commands<'this>;
-    type Lock<'state> = &'state CommandSender;
+    type Lock<'state> = &'state Mutex<CommandQueue>;
 
     fn lock<'state>(state: &SystemRunState<'state>) -> Self::Lock<'state> {
-        state.command_sender
+        state.commands
     }
 
     fn construct<'this>(state: &'this mut Self::Lock<'_>, last_run_tick: u64) -> Self::This<'this> {
         _ = last_run_tick;
-        Commands {
-            command_sender: state,
-        }
+        Commands { commands: state }
     }
 
     fn get_resource_types() -> impl Iterator<Item = crate::system::Borrow> {
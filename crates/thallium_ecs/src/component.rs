@@ -1,11 +1,49 @@
 use crate::{App, Entity};
+use std::marker::PhantomData;
 
 /// The trait implemented for all types that are used as components
-pub trait Component: Sized + Send + Sync + 'static {}
+pub trait Component: Sized + Send + Sync + 'static {
+    /// Returns the function used to clone this [`Component`] by [`App::clone_entity`], or [`None`] if it cannot be cloned
+    ///
+    /// `#[derive(Component)]` implements this for types without generic parameters that implement [`Clone`], other types can be registered with [`App::register_component_clone`]
+    fn clone_fn() -> Option<fn(&Self) -> Self> {
+        None
+    }
+}
+
+/// Used by `#[derive(Component)]` to find out if a type implements [`Clone`], [`CloneFnSpecialized`] is picked over [`CloneFnFallback`] by auto-ref when it applies
+#[doc(hidden)]
+pub struct CloneFnProbe<T>(pub PhantomData<T>);
+
+#[doc(hidden)]
+pub trait CloneFnSpecialized<T> {
+    fn clone_fn(&self) -> Option<fn(&T) -> T>;
+}
+
+impl<T> CloneFnSpecialized<T> for &CloneFnProbe<T>
+where
+    T: Clone,
+{
+    fn clone_fn(&self) -> Option<fn(&T) -> T> {
+        Some(T::clone)
+    }
+}
+
+#[doc(hidden)]
+pub trait CloneFnFallback<T> {
+    fn clone_fn(&self) -> Option<fn(&T) -> T>;
+}
+
+impl<T> CloneFnFallback<T> for CloneFnProbe<T> {
+    fn clone_fn(&self) -> Option<fn(&T) -> T> {
+        None
+    }
+}
 
 pub trait ComponentBundle: Sized + Send + Sync + 'static {
     fn add(self, app: &mut App, entity: Entity);
     fn remove(app: &mut App, entity: Entity);
+    fn type_names(names: &mut Vec<&'static str>);
 }
 
 impl<C> ComponentBundle for C
@@ -19,6 +57,10 @@ where
     fn remove(app: &mut App, entity: Entity) {
         app.remove_component::<C>(entity);
     }
+
+    fn type_names(names: &mut Vec<&'static str>) {
+        names.push(std::any::type_name::<C>());
+    }
 }
 
 macro_rules! component_bundle_tuple {
@@ -44,6 +86,13 @@ macro_rules! component_bundle_tuple {
                     $param::remove(app, entity);
                 )*
             }
+
+            fn type_names(names: &mut Vec<&'static str>) {
+                _ = names;
+                $(
+                    $param::type_names(names);
+                )*
+            }
         }
     };
 }
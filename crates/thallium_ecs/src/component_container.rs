@@ -5,6 +5,13 @@ pub(crate) trait DynComponentContainer: Send + Sync {
     fn as_any(&self) -> &dyn Any;
     fn as_any_mut(&mut self) -> &mut dyn Any;
     fn remove(&mut self, entity: Entity);
+    fn type_name(&self) -> &'static str;
+    fn is_cloneable(&self) -> bool;
+    /// Clones the component of `source` onto `target`, returning whether it was cloned
+    fn clone_component(&mut self, current_tick: u64, source: Entity, target: Entity) -> bool;
+    /// Clones every component in the container, keeping their ticks
+    fn try_clone(&self) -> Option<Box<dyn DynComponentContainer>>;
+    fn first_entity(&self) -> Option<Entity>;
 }
 
 impl<C> DynComponentContainer for ComponentContainer<C>
@@ -22,6 +29,62 @@ where
     fn remove(&mut self, entity: Entity) {
         self.remove(entity);
     }
+
+    fn type_name(&self) -> &'static str {
+        std::any::type_name::<C>()
+    }
+
+    fn is_cloneable(&self) -> bool {
+        self.clone_fn.is_some()
+    }
+
+    fn clone_component(&mut self, current_tick: u64, source: Entity, target: Entity) -> bool {
+        let Some(clone_fn) = self.clone_fn else {
+            return false;
+        };
+        let Some(component) = self
+            .get(current_tick, source)
+            .map(|component| clone_fn(&component))
+        else {
+            return false;
+        };
+        self.insert(current_tick, target, component);
+        true
+    }
+
+    fn try_clone(&self) -> Option<Box<dyn DynComponentContainer>> {
+        let clone_fn = self.clone_fn?;
+        Some(Box::new(ComponentContainer {
+            components: self
+                .components
+                .iter()
+                .map(|slot| {
+                    slot.as_ref().map(
+                        |&ComponentSlot {
+                             generation,
+                             ref component,
+                             last_modified_tick,
+                         }| ComponentSlot {
+                            generation,
+                            component: clone_fn(component),
+                            last_modified_tick,
+                        },
+                    )
+                })
+                .collect(),
+            clone_fn: Some(clone_fn),
+            last_borrowed_mut_tick: self.last_borrowed_mut_tick,
+        }))
+    }
+
+    fn first_entity(&self) -> Option<Entity> {
+        self.components.iter().enumerate().find_map(|(id, slot)| {
+            slot.as_ref().map(|slot| Entity {
+                id,
+                generation: slot.generation,
+            })
+        })
+    }
 }
 
 impl dyn DynComponentContainer {
@@ -58,6 +121,9 @@ where
     C: Component,
 {
     pub(crate) components: Vec<Option<ComponentSlot<C>>>,
+    pub(crate) clone_fn: Option<fn(&C) -> C>,
+    /// The last tick the container was borrowed mutably, no [`Component`] in it can have been modified after this
+    pub(crate) last_borrowed_mut_tick: u64,
 }
 
 impl<C> ComponentContainer<C>
@@ -67,6 +133,8 @@ where
     pub(crate) fn new() -> Self {
         Self {
             components: Vec::new(),
+            clone_fn: C::clone_fn(),
+            last_borrowed_mut_tick: 0,
         }
     }
 
@@ -151,11 +219,9 @@ where
                 if entities[i].id >= self.components.len() {
                     break;
                 }
-                if !self.components[entity.id]
+                if self.components[entity.id]
                     .as_ref()
-                    .map_or(false, |&ComponentSlot { generation, .. }| {
-                        generation == entity.generation
-                    })
+                    .is_none_or(|&ComponentSlot { generation, .. }| generation != entity.generation)
                 {
                     break;
                 }
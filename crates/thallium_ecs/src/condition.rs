@@ -0,0 +1,68 @@
+use crate::{
+    system::{merge_borrows, Borrow, SystemRunState},
+    App, IntoSystem, System,
+};
+
+/// A [`System`] that only runs when a condition [`System`] returns `true`, created by [`IntoSystem::run_if`]
+pub struct RunIf<S, C>
+where
+    S: System<Output = ()>,
+    C: System<Input = (), Output = bool>,
+{
+    pub(crate) system: S,
+    pub(crate) condition: C,
+}
+
+impl<S, C> System for RunIf<S, C>
+where
+    S: System<Output = ()>,
+    C: System<Input = (), Output = bool>,
+{
+    type Input = S::Input;
+    type Output = ();
+
+    fn run(&mut self, input: Self::Input, state: &SystemRunState<'_>) -> Self::Output {
+        if self.condition.run((), state) {
+            self.system.run(input, state);
+        }
+    }
+
+    fn apply_commands(&mut self, app: &mut App) {
+        self.condition.apply_commands(app);
+        self.system.apply_commands(app);
+    }
+
+    fn get_resource_types(&self) -> impl Iterator<Item = Borrow> + '_
+    where
+        Self: Sized,
+    {
+        merge_borrows(
+            self.condition
+                .get_resource_types()
+                .chain(self.system.get_resource_types()),
+        )
+    }
+
+    fn get_component_types(&self) -> impl Iterator<Item = Borrow> + '_
+    where
+        Self: Sized,
+    {
+        merge_borrows(
+            self.condition
+                .get_component_types()
+                .chain(self.system.get_component_types()),
+        )
+    }
+}
+
+impl<S, C> IntoSystem<()> for RunIf<S, C>
+where
+    S: System<Output = ()>,
+    C: System<Input = (), Output = bool>,
+{
+    type System = Self;
+
+    fn into_system(self) -> Self::System {
+        self
+    }
+}
@@ -0,0 +1,317 @@
+use crate::{system::SystemRunState, Resource, SystemHandle, SystemId};
+use parking_lot::Mutex;
+use std::{
+    collections::{HashMap, VecDeque},
+    fmt::Write,
+    time::{Duration, Instant},
+};
+
+/// Identifies a system whose timings are recorded by [`SystemDiagnostics`]
+#[derive(Debug, Clone, Copy, PartialEq, Eq, PartialOrd, Ord, Hash)]
+pub enum SystemKey {
+    /// A [`System`](crate::System) in a [`SystemSet`](crate::SystemSet) or schedule
+    Handle(SystemHandle),
+    /// A [`System`](crate::System) registered with [`App::register_system`](crate::App::register_system)
+    Id(SystemId),
+}
+
+impl From<SystemHandle> for SystemKey {
+    fn from(handle: SystemHandle) -> Self {
+        SystemKey::Handle(handle)
+    }
+}
+
+impl From<SystemId> for SystemKey {
+    fn from(id: SystemId) -> Self {
+        SystemKey::Id(id)
+    }
+}
+
+/// Systems without a [`SystemKey`] are the ones passed straight to [`App::run`](crate::App::run), so they are keyed by name
+#[derive(Clone, PartialEq, Eq, Hash)]
+enum StatsKey {
+    System(SystemKey),
+    Run(String),
+}
+
+pub(crate) struct SystemSample {
+    pub(crate) system: Option<SystemKey>,
+    pub(crate) name: &'static str,
+    pub(crate) wall_time: Duration,
+    pub(crate) lock_wait: Duration,
+}
+
+pub(crate) type DiagnosticsCollector = Mutex<Vec<SystemSample>>;
+
+/// Locks the state of a system with `lock` and runs it with `run`, recording timings if the [`SystemDiagnostics`] [`Resource`] exists
+pub(crate) fn measure<L, O>(
+    state: &SystemRunState<'_>,
+    name: &'static str,
+    lock: impl FnOnce() -> L,
+    run: impl FnOnce(L) -> O,
+) -> O {
+    let Some(diagnostics) = state.diagnostics else {
+        return run(lock());
+    };
+
+    let start = Instant::now();
+    let locks = lock();
+    let lock_wait = start.elapsed();
+    let output = run(locks);
+    diagnostics.lock().push(SystemSample {
+        system: state.system,
+        name,
+        wall_time: start.elapsed(),
+        lock_wait,
+    });
+    output
+}
+
+/// The timings recorded for a single system, see [`SystemDiagnostics`]
+#[derive(Clone)]
+pub struct SystemStats {
+    name: String,
+    run_count: u64,
+    wall_times: VecDeque<Duration>,
+    lock_waits: VecDeque<Duration>,
+}
+
+impl SystemStats {
+    /// Returns the [`std::any::type_name`] of the system function, piped systems are joined with ` | `
+    pub fn name(&self) -> &str {
+        &self.name
+    }
+
+    /// Returns the number of times the system has run since the [`SystemDiagnostics`] was added or cleared
+    pub fn run_count(&self) -> u64 {
+        self.run_count
+    }
+
+    /// Returns the wall time of the most recent run, this includes [`SystemStats::last_lock_wait`]
+    pub fn last_wall_time(&self) -> Duration {
+        self.wall_times.back().copied().unwrap_or_default()
+    }
+
+    /// Returns the time spent locking the parameters of the system in the most recent run
+    pub fn last_lock_wait(&self) -> Duration {
+        self.lock_waits.back().copied().unwrap_or_default()
+    }
+
+    /// Returns the average wall time over the recorded window
+    pub fn average_wall_time(&self) -> Duration {
+        average(&self.wall_times)
+    }
+
+    /// Returns the average time spent locking the parameters of the system over the recorded window
+    pub fn average_lock_wait(&self) -> Duration {
+        average(&self.lock_waits)
+    }
+
+    /// Returns the wall time at `percentile` (from `0.0` to `100.0`) over the recorded window
+    pub fn wall_time_percentile(&self, percentile: f64) -> Duration {
+        self::percentile(&self.wall_times, percentile)
+    }
+
+    /// Returns the time spent locking the parameters of the system at `percentile` (from `0.0` to `100.0`) over the recorded window
+    pub fn lock_wait_percentile(&self, percentile: f64) -> Duration {
+        self::percentile(&self.lock_waits, percentile)
+    }
+}
+
+fn average(samples: &VecDeque<Duration>) -> Duration {
+    if samples.is_empty() {
+        return Duration::ZERO;
+    }
+    samples.iter().sum::<Duration>() / samples.len() as u32
+}
+
+fn percentile(samples: &VecDeque<Duration>, percentile: f64) -> Duration {
+    if samples.is_empty() {
+        return Duration::ZERO;
+    }
+    let mut samples = samples.iter().copied().collect::<Vec<_>>();
+    samples.sort_unstable();
+    let rank = (percentile.clamp(0.0, 100.0) / 100.0 * samples.len() as f64).ceil() as usize;
+    samples[rank.saturating_sub(1)]
+}
+
+/// A [`Resource`] that records how long each system takes to run
+///
+/// Systems are only timed while this [`Resource`] exists in the [`App`](crate::App)
+///
+/// The timings are keyed by the [`SystemKey`] of the system rather than its [`std::any::type_name`], so that systems that share a name,
+/// such as closures or the same function added to several schedules, are kept apart, use [`SystemDiagnostics::get_by_name`] to look them up by name
+/// systems passed straight to [`App::run`](crate::App::run) have no [`SystemKey`] and are keyed by name instead
+#[derive(Clone)]
+pub struct SystemDiagnostics {
+    window: usize,
+    systems: HashMap<StatsKey, SystemStats>,
+}
+
+impl SystemDiagnostics {
+    /// Constructs an empty [`SystemDiagnostics`] that keeps the last 120 runs of each system for averages and percentiles
+    pub fn new() -> Self {
+        Self::with_window(120)
+    }
+
+    /// Constructs an empty [`SystemDiagnostics`] that keeps the last `window` runs of each system for averages and percentiles
+    pub fn with_window(window: usize) -> Self {
+        Self {
+            window: window.max(1),
+            systems: HashMap::new(),
+        }
+    }
+
+    /// Gets the [`SystemStats`] of the system with the [`SystemKey`] `system`
+    pub fn get(&self, system: impl Into<SystemKey>) -> Option<&SystemStats> {
+        self.systems.get(&StatsKey::System(system.into()))
+    }
+
+    /// Gets the [`SystemStats`] of a system with the name `name`, if several systems share the name the first one sorted by [`SystemKey`] is returned
+    pub fn get_by_name(&self, name: &str) -> Option<&SystemStats> {
+        self.iter()
+            .find(|(system_name, _)| *system_name == name)
+            .map(|(_, stats)| stats)
+    }
+
+    /// Returns an iterator over the names and [`SystemStats`] of all systems that have been recorded, sorted by name
+    pub fn iter(&self) -> impl Iterator<Item = (&str, &SystemStats)> + '_ {
+        let mut systems = self.systems.iter().collect::<Vec<_>>();
+        systems.sort_by(|(a_key, a), (b_key, b)| {
+            let key = |key: &StatsKey| match key {
+                StatsKey::System(system) => Some(*system),
+                StatsKey::Run(_) => None,
+            };
+            a.name.cmp(&b.name).then(key(a_key).cmp(&key(b_key)))
+        });
+        systems
+            .into_iter()
+            .map(|(_, stats)| (stats.name.as_str(), stats))
+    }
+
+    /// Removes all recorded timings
+    pub fn clear(&mut self) {
+        self.systems.clear();
+    }
+
+    pub(crate) fn record(&mut self, samples: Vec<SystemSample>) {
+        // each system runs at most once per batch, so the samples of a piped system are merged into a single run
+        let mut runs: Vec<(Option<SystemKey>, String, Duration, Duration)> = Vec::new();
+        for sample in samples {
+            match runs
+                .iter_mut()
+                .find(|(system, ..)| *system == sample.system)
+            {
+                Some((_, name, wall_time, lock_wait)) => {
+                    name.push_str(" | ");
+                    name.push_str(sample.name);
+                    *wall_time += sample.wall_time;
+                    *lock_wait += sample.lock_wait;
+                }
+                None => runs.push((
+                    sample.system,
+                    sample.name.to_owned(),
+                    sample.wall_time,
+                    sample.lock_wait,
+                )),
+            }
+        }
+
+        for (system, name, wall_time, lock_wait) in runs {
+            let key = match system {
+                Some(system) => StatsKey::System(system),
+                None => StatsKey::Run(name.clone()),
+            };
+            let stats = self.systems.entry(key).or_insert_with(|| SystemStats {
+                name,
+                run_count: 0,
+                wall_times: VecDeque::new(),
+                lock_waits: VecDeque::new(),
+            });
+            stats.run_count += 1;
+            if stats.wall_times.len() == self.window {
+                stats.wall_times.pop_front();
+                stats.lock_waits.pop_front();
+            }
+            stats.wall_times.push_back(wall_time);
+            stats.lock_waits.push_back(lock_wait);
+        }
+    }
+
+    /// Formats the recorded timings as a human readable table
+    pub fn table(&self) -> String {
+        let name_width = self
+            .systems
+            .values()
+            .map(|stats| stats.name.len())
+            .max()
+            .unwrap_or(0)
+            .max("system".len());
+
+        let mut table = String::new();
+        _ = writeln!(
+            table,
+            "{:name_width$} | {:>8} | {:>12} | {:>12} | {:>12} | {:>12} | {:>12}",
+            "system", "runs", "avg", "p50", "p95", "p99", "avg lock",
+        );
+        _ = writeln!(table, "{:-<1$}", "", name_width + 91);
+        for (name, stats) in self.iter() {
+            _ = writeln!(
+                table,
+                "{:name_width$} | {:>8} | {:>12.2?} | {:>12.2?} | {:>12.2?} | {:>12.2?} | {:>12.2?}",
+                name,
+                stats.run_count,
+                stats.average_wall_time(),
+                stats.wall_time_percentile(50.0),
+                stats.wall_time_percentile(95.0),
+                stats.wall_time_percentile(99.0),
+                stats.average_lock_wait(),
+            );
+        }
+        table
+    }
+
+    /// Formats the recorded timings as JSON, all durations are in nanoseconds
+    pub fn json(&self) -> String {
+        let mut json = String::from("{\"systems\":[");
+        for (i, (name, stats)) in self.iter().enumerate() {
+            if i != 0 {
+                json.push(',');
+            }
+            json.push_str("{\"name\":\"");
+            for c in name.chars() {
+                match c {
+                    '"' => json.push_str("\\\""),
+                    '\\' => json.push_str("\\\\"),
+                    c if c.is_control() => _ = write!(json, "\\u{:04x}", c as u32),
+                    c => json.push(c),
+                }
+            }
+            _ = write!(
+                json,
+                "\",\"runs\":{},\"average_wall_time\":{},\"p50_wall_time\":{},\"p95_wall_time\":{},\"p99_wall_time\":{},\"average_lock_wait\":{},\"p99_lock_wait\":{}}}",
+                stats.run_count,
+                stats.average_wall_time().as_nanos(),
+                stats.wall_time_percentile(50.0).as_nanos(),
+                stats.wall_time_percentile(95.0).as_nanos(),
+                stats.wall_time_percentile(99.0).as_nanos(),
+                stats.average_lock_wait().as_nanos(),
+                stats.lock_wait_percentile(99.0).as_nanos(),
+            );
+        }
+        json.push_str("]}");
+        json
+    }
+}
+
+impl Default for SystemDiagnostics {
+    fn default() -> Self {
+        Self::new()
+    }
+}
+
+impl Resource for SystemDiagnostics {
+    fn resource_clone_fn() -> Option<fn(&Self) -> Self> {
+        Some(Self::clone)
+    }
+}
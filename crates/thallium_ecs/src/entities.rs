@@ -1,20 +1,50 @@
 use crate::{
     system::{Borrow, SystemRunState},
-    SystemParameter,
+    Component, SystemParameter,
 };
-use std::{any::TypeId, collections::HashSet, num::NonZeroUsize};
+use std::{any::TypeId, collections::HashSet, fmt, num::NonZeroUsize};
 
 /// A handle for components to be attached to
 ///
 /// The behaviour of using an [`Entity`] with the wrong [`App`](crate::App) is unspecified but not UB
-#[derive(Debug, Clone, Copy, PartialEq, Eq, PartialOrd, Ord, Hash)]
+///
+/// An [`Entity`] is printed as `Entity(12v3)`, its id followed by how many times that id has been used, use [`App::entity_label`](crate::App::entity_label) to include its [`Name`](crate::Name)
+#[derive(Clone, Copy, PartialEq, Eq, PartialOrd, Ord, Hash)]
 pub struct Entity {
     pub(crate) id: usize,
     pub(crate) generation: NonZeroUsize,
 }
 
+impl Entity {
+    /// Starts at 1 and counts up each time the id is reused, the generation is even while the [`Entity`] exists and odd once it is destroyed
+    pub(crate) fn version(self) -> usize {
+        self.generation.get() / 2
+    }
+}
+
+impl fmt::Debug for Entity {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        write!(f, "Entity({}v{})", self.id, self.version())
+    }
+}
+
+/// A [`Component`] that hides its [`Entity`] from [`Query`](crate::Query)s and [`Entities`] without destroying it
+///
+/// Disabled [`Entity`]s are still seen by [`Query`](crate::Query)s that include [`Disabled`] in their parameters, or that opt in with [`Query::including_disabled`](crate::Query::including_disabled)
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Hash, Default)]
+pub struct Disabled;
+
+impl Component for Disabled {
+    fn clone_fn() -> Option<fn(&Self) -> Self> {
+        Some(Self::clone)
+    }
+}
+
+#[derive(Clone)]
 pub struct EntityMap {
     entities: Vec<(NonZeroUsize, HashSet<TypeId>)>,
+    /// Whether each [`Entity`] has [`Disabled`], kept separately so that queries do not need to lock the [`Disabled`] container
+    disabled: Vec<bool>,
     next_free_entity: usize,
 }
 
@@ -22,6 +52,7 @@ impl EntityMap {
     pub(crate) fn new() -> Self {
         Self {
             entities: Vec::new(),
+            disabled: Vec::new(),
             next_free_entity: 0,
         }
     }
@@ -48,6 +79,7 @@ impl EntityMap {
             };
 
             self.entities.push((NEW_GENERATION, HashSet::new()));
+            self.disabled.push(false);
             self.next_free_entity = self.entities.len();
             Entity {
                 id,
@@ -62,6 +94,7 @@ impl EntityMap {
             if entity.id < self.next_free_entity {
                 self.next_free_entity = entity.id;
             }
+            self.disabled[entity.id] = false;
             Some(std::mem::take(&mut self.entities[entity.id].1))
         } else {
             None
@@ -71,26 +104,41 @@ impl EntityMap {
     pub(crate) fn entity_exists(&self, entity: Entity) -> bool {
         self.entities
             .get(entity.id)
-            .map_or(false, |&(generation, _)| generation == entity.generation)
+            .is_some_and(|&(generation, _)| generation == entity.generation)
+    }
+
+    pub(crate) fn is_disabled(&self, entity: Entity) -> bool {
+        self.entity_exists(entity) && self.disabled[entity.id]
+    }
+
+    pub(crate) fn components(&self, entity: Entity) -> impl Iterator<Item = TypeId> + '_ {
+        debug_assert_eq!(self.entities[entity.id].0, entity.generation);
+        self.entities[entity.id].1.iter().copied()
     }
 
     pub(crate) fn add_component(&mut self, entity: Entity, component_type: TypeId) {
         debug_assert_eq!(self.entities[entity.id].0, entity.generation);
         self.entities[entity.id].1.insert(component_type);
+        if component_type == TypeId::of::<Disabled>() {
+            self.disabled[entity.id] = true;
+        }
     }
 
     pub(crate) fn remove_component(&mut self, entity: Entity, component_type: TypeId) {
         debug_assert_eq!(self.entities[entity.id].0, entity.generation);
         self.entities[entity.id].1.remove(&component_type);
+        if component_type == TypeId::of::<Disabled>() {
+            self.disabled[entity.id] = false;
+        }
     }
 
-    pub(crate) fn iter(&self) -> impl Iterator<Item = Option<Entity>> + '_ {
-        self.entities
-            .iter()
-            .enumerate()
-            .map(|(id, &(generation, _))| {
-                (generation.get() & 1 == 0).then_some(Entity { id, generation })
-            })
+    pub(crate) fn iter(&self, include_disabled: bool) -> impl Iterator<Item = Option<Entity>> + '_ {
+        self.entities.iter().zip(&self.disabled).enumerate().map(
+            move |(id, (&(generation, _), &disabled))| {
+                (generation.get() & 1 == 0 && (include_disabled || !disabled))
+                    .then_some(Entity { id, generation })
+            },
+        )
     }
 }
 
@@ -100,10 +148,11 @@ impl Default for EntityMap {
     }
 }
 
-/// A [`SystemParameter`] for getting a list of all alive [`Entity`]s
+/// A [`SystemParameter`] for getting a list of all alive [`Entity`]s, [`Disabled`] [`Entity`]s are skipped unless [`Entities::including_disabled`] is used
 #[derive(Clone, Copy)]
 pub struct Entities<'a> {
     entities: &'a EntityMap,
+    include_disabled: bool,
 }
 
 impl<'a> Entities<'a> {
@@ -114,7 +163,15 @@ impl<'a> Entities<'a> {
 
     /// Returns an iterator over all alive [`Entity`]s
     pub fn iter(&self) -> impl Iterator<Item = Entity> + 'a {
-        self.entities.iter().flatten()
+        self.entities.iter(self.include_disabled).flatten()
+    }
+
+    /// Makes [`Entities::iter`] include [`Disabled`] [`Entity`]s
+    pub fn including_disabled(self) -> Self {
+        Self {
+            include_disabled: true,
+            ..self
+        }
     }
 }
 
@@ -128,7 +185,10 @@ impl<'a> SystemParameter for Entities<'a> {
 
     fn construct<'this>(state: &'this mut Self::Lock<'_>, last_run_tick: u64) -> Self::This<'this> {
         _ = last_run_tick;
-        Entities { entities: state }
+        Entities {
+            entities: state,
+            include_disabled: false,
+        }
     }
 
     fn get_resource_types() -> impl Iterator<Item = Borrow> {
@@ -0,0 +1,287 @@
+use crate::{
+    component_container::ComponentContainer,
+    name::fmt_label,
+    resource_container::{DynResourceContainer, ResourceContainer},
+    App, Children, CommandError, CommandErrorKind, Component, Entity, EntityCommands, Name,
+    Resource,
+};
+use parking_lot::RwLock;
+use std::{any::TypeId, fmt};
+
+/// What [`App::clone_entity_with`] does with [`Component`]s that have no clone function
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Hash, Default)]
+pub enum NonCloneable {
+    /// The [`Component`] is left off the clone
+    #[default]
+    Skip,
+    /// Nothing is cloned and a [`CloneError::NotCloneable`] is returned
+    Error,
+}
+
+/// Options for [`App::clone_entity_with`]
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Hash, Default)]
+pub struct CloneOptions {
+    /// What to do with [`Component`]s that have no clone function
+    pub non_cloneable: NonCloneable,
+    /// Whether the [`Children`] are cloned as well, the clones are added as children of the cloned [`Entity`]
+    pub recursive: bool,
+}
+
+/// The error returned by [`App::clone_entity_with`] and [`App::try_clone`]
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub enum CloneError {
+    /// The [`Entity`] being cloned does not exist
+    EntityDoesNotExist(Entity),
+    /// An [`Entity`] has a [`Component`] without a clone function, and [`NonCloneable::Error`] was used or the whole [`App`] was being cloned
+    NotCloneable {
+        /// The [`Entity`] that has the [`Component`]
+        entity: Entity,
+        /// The [`Name`] of the [`Entity`] if it has one
+        name: Option<Name>,
+        /// The [`std::any::type_name`] of the [`Component`]
+        component: &'static str,
+    },
+    /// A [`Resource`] without a clone function exists in the [`App`] being cloned
+    ResourceNotCloneable {
+        /// The [`std::any::type_name`] of the [`Resource`]
+        resource: &'static str,
+    },
+}
+
+impl fmt::Display for CloneError {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        match self {
+            CloneError::EntityDoesNotExist(entity) => {
+                write!(f, "cannot clone {entity:?}, the entity does not exist")
+            }
+            CloneError::NotCloneable {
+                entity,
+                name,
+                component,
+            } => {
+                write!(f, "cannot clone ")?;
+                fmt_label(f, *entity, name.as_ref())?;
+                write!(f, ", the component {component} is not cloneable")
+            }
+            CloneError::ResourceNotCloneable { resource } => {
+                write!(
+                    f,
+                    "cannot clone the app, the resource {resource} is not cloneable"
+                )
+            }
+        }
+    }
+}
+
+impl std::error::Error for CloneError {}
+
+/// An entry in the [`Resource`] clone registry of the [`App`]
+#[derive(Clone, Copy)]
+pub(crate) struct ResourceClone {
+    pub(crate) clone: Option<fn(&dyn DynResourceContainer) -> Box<dyn DynResourceContainer>>,
+}
+
+impl ResourceClone {
+    pub(crate) fn new<R>() -> Self
+    where
+        R: Resource,
+    {
+        Self {
+            clone: R::resource_clone_fn().map(|_| clone_resource::<R> as _),
+        }
+    }
+}
+
+fn clone_resource<R>(resource: &dyn DynResourceContainer) -> Box<dyn DynResourceContainer>
+where
+    R: Resource,
+{
+    clone_resource_with::<R>(resource, R::resource_clone_fn().unwrap())
+}
+
+fn clone_resource_with<R>(
+    resource: &dyn DynResourceContainer,
+    clone_fn: fn(&R) -> R,
+) -> Box<dyn DynResourceContainer>
+where
+    R: Resource,
+{
+    let container = resource.downcast_ref::<R>();
+    Box::new(ResourceContainer {
+        resource: clone_fn(&container.resource),
+        last_modified_tick: container.last_modified_tick,
+    })
+}
+
+impl App {
+    /// Registers a [`Component`] that implements [`Clone`] so that it can be cloned by [`App::clone_entity`], replacing its [`Component::clone_fn`]
+    pub fn register_component_clone<C>(&mut self)
+    where
+        C: Component + Clone,
+    {
+        self.components
+            .entry(TypeId::of::<C>())
+            .or_insert_with(|| RwLock::new(Box::new(ComponentContainer::<C>::new())))
+            .get_mut()
+            .downcast_mut::<C>()
+            .clone_fn = Some(C::clone);
+    }
+
+    /// Registers a [`Resource`] that implements [`Clone`] so that it can be cloned by [`App::try_clone`], replacing its [`Resource::resource_clone_fn`]
+    pub fn register_resource_clone<R>(&mut self)
+    where
+        R: Resource + Clone,
+    {
+        self.resource_clones.insert(
+            TypeId::of::<R>(),
+            ResourceClone {
+                clone: Some(|resource| clone_resource_with::<R>(resource, R::clone)),
+            },
+        );
+    }
+
+    /// Creates a new [`Entity`] with clones of all the [`Component`]s on `source`, skipping any [`Component`]s that cannot be cloned
+    ///
+    /// # Panics
+    /// Panics if `source` does not exist
+    pub fn clone_entity(&mut self, source: Entity) -> Entity {
+        match self.clone_entity_with(source, CloneOptions::default()) {
+            Ok(entity) => entity,
+            Err(error) => panic!("{error}"),
+        }
+    }
+
+    /// Creates a new [`Entity`] with clones of all the [`Component`]s on `source`, nothing is created if an error is returned
+    ///
+    /// [`Relationship`](crate::Relationship)s are cloned like any other [`Component`] so the clone has the same targets,
+    /// but [`RelationshipTarget`](crate::RelationshipTarget)s are never cloned because each source can only have one target
+    pub fn clone_entity_with(
+        &mut self,
+        source: Entity,
+        options: CloneOptions,
+    ) -> Result<Entity, CloneError> {
+        if !self.entity_exists(source) {
+            return Err(CloneError::EntityDoesNotExist(source));
+        }
+        if options.non_cloneable == NonCloneable::Error {
+            self.check_cloneable(source, options.recursive)?;
+        }
+        Ok(self.clone_entity_unchecked(source, options.recursive))
+    }
+
+    fn check_cloneable(&mut self, entity: Entity, recursive: bool) -> Result<(), CloneError> {
+        for component in self.entities.components(entity) {
+            if !self.is_cloned(component) {
+                continue;
+            }
+            let container = self.components[&component].read();
+            if !container.is_cloneable() {
+                return Err(CloneError::NotCloneable {
+                    entity,
+                    name: self.entity_name(entity),
+                    component: container.type_name(),
+                });
+            }
+        }
+
+        if recursive {
+            let children = self.children_of(entity);
+            for child in children {
+                self.check_cloneable(child, recursive)?;
+            }
+        }
+        Ok(())
+    }
+
+    fn clone_entity_unchecked(&mut self, source: Entity, recursive: bool) -> Entity {
+        let target = self.create_entity();
+        let components = self
+            .entities
+            .components(source)
+            .filter(|&component| self.is_cloned(component))
+            .collect::<Vec<_>>();
+        let tick = self.current_tick() + 1;
+        let mut hooks = Vec::new();
+        for component in components {
+            let cloned = self
+                .components
+                .get_mut(&component)
+                .unwrap()
+                .get_mut()
+                .clone_component(tick, source, target);
+            if cloned {
+                self.entities.add_component(target, component);
+                hooks.extend(
+                    self.component_hooks
+                        .get(&component)
+                        .and_then(|hooks| hooks.on_insert),
+                );
+            }
+        }
+        // the hooks run once everything is cloned, the same as if the components were added one at a time
+        for on_insert in hooks {
+            on_insert(self, target);
+        }
+
+        if recursive {
+            for child in self.children_of(source) {
+                let clone = self.clone_entity_unchecked(child, recursive);
+                self.set_parent(clone, target);
+            }
+        }
+        target
+    }
+
+    fn is_cloned(&self, component: TypeId) -> bool {
+        self.component_hooks
+            .get(&component)
+            .is_none_or(|hooks| hooks.cloned)
+    }
+
+    fn children_of(&mut self, entity: Entity) -> Vec<Entity> {
+        self.component::<Children>(entity)
+            .map(|children| children.to_vec())
+            .unwrap_or_default()
+    }
+}
+
+impl EntityCommands<'_, '_> {
+    /// Spawns a clone of the [`Entity`] once the commands are applied, then calls `f` with the clone, see [`App::clone_entity`]
+    /// This reports a [`CommandError`] if the [`Entity`] has been destroyed, in which case `f` is not called
+    pub fn clone_and_spawn<F>(&mut self, f: F)
+    where
+        F: FnOnce(&mut App, Entity) + Send + 'static,
+    {
+        self.clone_and_spawn_with(false, f);
+    }
+
+    /// Spawns a clone of the [`Entity`] along with clones of all of its descendants once the commands are applied, then calls `f` with the clone, see [`App::clone_entity_with`]
+    /// This reports a [`CommandError`] if the [`Entity`] has been destroyed, in which case `f` is not called
+    pub fn clone_and_spawn_recursive<F>(&mut self, f: F)
+    where
+        F: FnOnce(&mut App, Entity) + Send + 'static,
+    {
+        self.clone_and_spawn_with(true, f);
+    }
+
+    fn clone_and_spawn_with<F>(&mut self, recursive: bool, f: F)
+    where
+        F: FnOnce(&mut App, Entity) + Send + 'static,
+    {
+        let source = self.id();
+        self.commands().add(move |app: &mut App| {
+            let options = CloneOptions {
+                non_cloneable: NonCloneable::Skip,
+                recursive,
+            };
+            match app.clone_entity_with(source, options) {
+                Ok(clone) => f(app, clone),
+                Err(_) => app.report_command_error(CommandError {
+                    kind: CommandErrorKind::CloneEntity,
+                    entity: source,
+                    components: vec![],
+                }),
+            }
+        });
+    }
+}
@@ -0,0 +1,80 @@
+enum ExecutorKind {
+    SingleThreaded,
+    #[cfg(feature = "multi_threaded")]
+    Global,
+    #[cfg(feature = "multi_threaded")]
+    ThreadPool(rayon::ThreadPool),
+}
+
+/// Decides how the [`System`](crate::System)s in a [`SystemSet`](crate::SystemSet) are run, set with [`App::set_executor`](crate::App::set_executor)
+pub struct Executor {
+    kind: ExecutorKind,
+}
+
+impl Executor {
+    /// Constructs an [`Executor`] that runs all [`System`](crate::System)s on the calling thread, one after another in the order they were registered
+    pub fn single_threaded() -> Self {
+        Self {
+            kind: ExecutorKind::SingleThreaded,
+        }
+    }
+
+    /// Constructs an [`Executor`] that runs [`System`](crate::System)s in parallel on the global [`rayon`] thread pool
+    #[cfg(feature = "multi_threaded")]
+    pub fn global() -> Self {
+        Self {
+            kind: ExecutorKind::Global,
+        }
+    }
+
+    /// Constructs an [`Executor`] that runs [`System`](crate::System)s in parallel on a dedicated [`rayon`] thread pool with `num_threads` threads
+    #[cfg(feature = "multi_threaded")]
+    pub fn thread_pool(num_threads: usize) -> Result<Self, rayon::ThreadPoolBuildError> {
+        Ok(Self::from_thread_pool(
+            rayon::ThreadPoolBuilder::new()
+                .num_threads(num_threads)
+                .build()?,
+        ))
+    }
+
+    /// Constructs an [`Executor`] that runs [`System`](crate::System)s in parallel on `thread_pool`
+    #[cfg(feature = "multi_threaded")]
+    pub fn from_thread_pool(thread_pool: rayon::ThreadPool) -> Self {
+        Self {
+            kind: ExecutorKind::ThreadPool(thread_pool),
+        }
+    }
+
+    /// Returns whether this [`Executor`] runs all [`System`](crate::System)s on the calling thread
+    pub fn is_single_threaded(&self) -> bool {
+        matches!(self.kind, ExecutorKind::SingleThreaded)
+    }
+
+    pub(crate) fn for_each<T>(&self, items: &mut [T], f: impl Fn(&mut T) + Send + Sync)
+    where
+        T: Send,
+    {
+        #[cfg(feature = "multi_threaded")]
+        use rayon::iter::{IntoParallelRefMutIterator, ParallelIterator};
+
+        match self.kind {
+            ExecutorKind::SingleThreaded => items.iter_mut().for_each(f),
+            #[cfg(feature = "multi_threaded")]
+            ExecutorKind::Global => items.par_iter_mut().for_each(f),
+            #[cfg(feature = "multi_threaded")]
+            ExecutorKind::ThreadPool(ref thread_pool) => {
+                thread_pool.install(|| items.par_iter_mut().for_each(f))
+            }
+        }
+    }
+}
+
+impl Default for Executor {
+    /// Returns [`Executor::global`] if the `multi_threaded` feature is enabled, otherwise [`Executor::single_threaded`]
+    fn default() -> Self {
+        #[cfg(feature = "multi_threaded")]
+        return Self::global();
+        #[cfg(not(feature = "multi_threaded"))]
+        return Self::single_threaded();
+    }
+}
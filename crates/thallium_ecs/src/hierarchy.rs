@@ -0,0 +1,309 @@
+use crate::{
+    commands::EntityCommands, component::ComponentBundle, App, Command, CommandError,
+    CommandErrorKind, Commands, Component, Entity, Query, Ref, Relationship, RelationshipCleanup,
+    RelationshipTarget,
+};
+use std::{collections::VecDeque, ops::Deref};
+
+/// A [`Component`] that stores the parent of an [`Entity`], this is kept in sync with [`Children`] by [`App::set_parent`] and [`App::remove_parent`]
+#[derive(Debug, Clone)]
+pub struct Parent(pub(crate) Entity);
+
+impl Parent {
+    /// Returns the parent [`Entity`]
+    pub fn get(&self) -> Entity {
+        self.0
+    }
+}
+
+impl Component for Parent {
+    fn clone_fn() -> Option<fn(&Self) -> Self> {
+        Some(Self::clone)
+    }
+}
+
+impl Relationship for Parent {
+    type Target = Children;
+
+    const CLEANUP: RelationshipCleanup = RelationshipCleanup::RemoveRelation;
+
+    fn from_target(target: Entity) -> Self {
+        Self(target)
+    }
+
+    fn target(&self) -> Entity {
+        self.0
+    }
+}
+
+/// A [`Component`] that stores the children of an [`Entity`] in the order they were added, this is kept in sync with [`Parent`] by [`App::set_parent`] and [`App::remove_parent`]
+#[derive(Debug)]
+pub struct Children(pub(crate) Vec<Entity>);
+
+impl Deref for Children {
+    type Target = [Entity];
+
+    fn deref(&self) -> &Self::Target {
+        &self.0
+    }
+}
+
+impl Component for Children {}
+
+impl RelationshipTarget for Children {
+    type Relationship = Parent;
+
+    fn from_sources(sources: Vec<Entity>) -> Self {
+        Self(sources)
+    }
+
+    fn sources(&self) -> &[Entity] {
+        &self.0
+    }
+
+    fn sources_mut(&mut self) -> &mut Vec<Entity> {
+        &mut self.0
+    }
+}
+
+impl App {
+    /// Makes `parent` the parent of `child`, removing `child` from the [`Children`] of its previous parent
+    /// This reports a [`CommandError`] if either [`Entity`] does not exist
+    ///
+    /// # Panics
+    /// Panics if `parent` is `child` or one of its descendants
+    pub fn set_parent(&mut self, child: Entity, parent: Entity) {
+        for entity in [child, parent] {
+            if !self.entity_exists(entity) {
+                self.report_command_error(CommandError {
+                    kind: CommandErrorKind::SetParent,
+                    entity,
+                    components: vec![],
+                });
+                return;
+            }
+        }
+
+        let mut ancestor = Some(parent);
+        while let Some(entity) = ancestor {
+            assert_ne!(
+                entity,
+                child,
+                "cannot make {:?} the parent of {:?}, because it would create a cycle",
+                self.entity_label(parent),
+                self.entity_label(child),
+            );
+            ancestor = self.component::<Parent>(entity).map(Parent::get);
+        }
+
+        self.relate::<Parent>(child, parent);
+    }
+
+    /// Makes `parent` the parent of `child`, the same as [`App::set_parent`]
+    pub fn add_child(&mut self, parent: Entity, child: Entity) {
+        self.set_parent(child, parent);
+    }
+
+    /// Removes the [`Parent`] of `child` and removes `child` from the [`Children`] of its parent, returning the parent
+    pub fn remove_parent(&mut self, child: Entity) -> Option<Entity> {
+        self.unrelate::<Parent>(child)
+    }
+
+    /// Destroys an [`Entity`] along with all of its descendants
+    /// This does not error if the [`Entity`] is already destroyed
+    pub fn despawn_recursive(&mut self, entity: Entity) {
+        self.remove_parent(entity);
+        let mut stack = vec![entity];
+        while let Some(entity) = stack.pop() {
+            if let Some(Children(children)) = self.remove_component::<Children>(entity) {
+                stack.extend(children);
+            }
+            self.destroy_entity(entity);
+        }
+    }
+}
+
+pub(crate) struct SetParent {
+    pub(crate) child: Entity,
+    pub(crate) parent: Entity,
+}
+
+impl Command for SetParent {
+    fn apply(self, app: &mut App) {
+        app.set_parent(self.child, self.parent);
+    }
+}
+
+pub(crate) struct SpawnChild<B> {
+    pub(crate) parent: Entity,
+    pub(crate) bundle: B,
+}
+
+impl<B> Command for SpawnChild<B>
+where
+    B: ComponentBundle,
+{
+    fn apply(self, app: &mut App) {
+        if !app.entity_exists(self.parent) {
+            let mut components = Vec::new();
+            B::type_names(&mut components);
+            app.report_command_error(CommandError {
+                kind: CommandErrorKind::SetParent,
+                entity: self.parent,
+                components,
+            });
+            return;
+        }
+        let child = app.create_entity();
+        self.bundle.add(app, child);
+        app.set_parent(child, self.parent);
+    }
+}
+
+impl Commands<'_> {
+    /// Makes `parent` the parent of `child`, see [`App::set_parent`]
+    pub fn set_parent(&mut self, child: Entity, parent: Entity) {
+        self.add(SetParent { child, parent });
+    }
+
+    /// Makes `parent` the parent of `child`, see [`App::add_child`]
+    pub fn add_child(&mut self, parent: Entity, child: Entity) {
+        self.add(SetParent { child, parent });
+    }
+
+    /// Removes the [`Parent`] of `child`, see [`App::remove_parent`]
+    pub fn remove_parent(&mut self, child: Entity) {
+        self.add(move |app: &mut App| _ = app.remove_parent(child));
+    }
+
+    /// Destroys an [`Entity`] along with all of its descendants, see [`App::despawn_recursive`]
+    pub fn despawn_recursive(&mut self, entity: Entity) {
+        self.add(move |app: &mut App| app.despawn_recursive(entity));
+    }
+}
+
+impl<'b> EntityCommands<'_, 'b> {
+    /// Makes `parent` the parent of the [`Entity`], see [`App::set_parent`]
+    pub fn set_parent(&mut self, parent: Entity) -> &mut Self {
+        let child = self.id();
+        self.commands().set_parent(child, parent);
+        self
+    }
+
+    /// Makes the [`Entity`] the parent of `child`, see [`App::add_child`]
+    pub fn add_child(&mut self, child: Entity) -> &mut Self {
+        let parent = self.id();
+        self.commands().add_child(parent, child);
+        self
+    }
+
+    /// Removes the [`Parent`] of the [`Entity`], see [`App::remove_parent`]
+    pub fn remove_parent(&mut self) -> &mut Self {
+        let child = self.id();
+        self.commands().remove_parent(child);
+        self
+    }
+
+    /// Destroys the [`Entity`] along with all of its descendants, see [`App::despawn_recursive`]
+    pub fn despawn_recursive(&mut self) {
+        let entity = self.id();
+        self.commands().despawn_recursive(entity);
+    }
+
+    /// Spawns children of the [`Entity`] with a [`ChildBuilder`]
+    pub fn with_children(&mut self, f: impl FnOnce(&mut ChildBuilder<'_, 'b>)) -> &mut Self {
+        let parent = self.id();
+        f(&mut ChildBuilder {
+            parent,
+            commands: self.commands(),
+        });
+        self
+    }
+}
+
+/// Queues commands that spawn children of an [`Entity`], created by [`EntityCommands::with_children`]
+pub struct ChildBuilder<'a, 'b> {
+    parent: Entity,
+    commands: &'a mut Commands<'b>,
+}
+
+impl<'b> ChildBuilder<'_, 'b> {
+    /// Returns the [`Entity`] that the children are spawned under
+    pub fn parent_entity(&self) -> Entity {
+        self.parent
+    }
+
+    /// Spawns a child with a bundle of [`Component`]s
+    /// This reports a [`CommandError`] instead of spawning the child if the parent has been destroyed
+    pub fn spawn<B>(&mut self, bundle: B) -> &mut Self
+    where
+        B: ComponentBundle,
+    {
+        self.commands.add(SpawnChild {
+            parent: self.parent,
+            bundle,
+        });
+        self
+    }
+
+    /// Returns the [`Commands`] that the children are spawned with
+    pub fn commands(&mut self) -> &mut Commands<'b> {
+        self.commands
+    }
+}
+
+impl<'a, 'r> Query<'a, Ref<'r, Parent>> {
+    /// Returns an iterator over the ancestors of `entity`, starting with its parent
+    ///
+    /// [`Disabled`](crate::Disabled) ancestors are walked through, but they are only returned if the [`Query`] includes them
+    pub fn iter_ancestors<'b>(
+        &'b self,
+        entity: Entity,
+    ) -> impl Iterator<Item = Entity> + use<'a, 'r, 'b> {
+        std::iter::successors(
+            self.get_including_disabled(entity).map(|parent| parent.0),
+            |&entity| self.get_including_disabled(entity).map(|parent| parent.0),
+        )
+        .filter(move |&entity| !self.is_hidden(entity))
+    }
+}
+
+impl<'a, 'r> Query<'a, Ref<'r, Children>> {
+    /// Returns an iterator over the descendants of `entity` in breadth first order, not including `entity` itself
+    ///
+    /// [`Disabled`](crate::Disabled) descendants are walked through, but they are only returned if the [`Query`] includes them
+    pub fn iter_descendants<'b>(
+        &'b self,
+        entity: Entity,
+    ) -> impl Iterator<Item = Entity> + use<'a, 'r, 'b> {
+        let mut queue = VecDeque::from([entity]);
+        std::iter::from_fn(move || {
+            let entity = queue.pop_front()?;
+            if let Some(children) = self.get_including_disabled(entity) {
+                queue.extend(children.iter().copied());
+            }
+            Some(entity)
+        })
+        .skip(1)
+        .filter(move |&entity| !self.is_hidden(entity))
+    }
+
+    /// Returns an iterator over the descendants of `entity` in depth first order, not including `entity` itself
+    ///
+    /// [`Disabled`](crate::Disabled) descendants are walked through, but they are only returned if the [`Query`] includes them
+    pub fn iter_descendants_depth_first<'b>(
+        &'b self,
+        entity: Entity,
+    ) -> impl Iterator<Item = Entity> + use<'a, 'r, 'b> {
+        let mut stack = vec![entity];
+        std::iter::from_fn(move || {
+            let entity = stack.pop()?;
+            if let Some(children) = self.get_including_disabled(entity) {
+                stack.extend(children.iter().rev().copied());
+            }
+            Some(entity)
+        })
+        .skip(1)
+        .filter(move |&entity| !self.is_hidden(entity))
+    }
+}
@@ -1,32 +1,78 @@
 #![doc = include_str!("../README.md")]
 
 mod app;
+mod command_error;
+mod command_queue;
 mod commands;
 mod component;
 mod component_container;
+mod condition;
+mod diagnostics;
 mod entities;
+mod entity_clone;
+mod executor;
+mod hierarchy;
+mod name;
+mod pipe;
 mod query;
 mod query_parameters;
+mod relationship;
 mod resource;
 mod resource_container;
+mod schedule;
+mod state;
 mod system;
 mod system_parameters;
 mod system_set;
+mod time;
+mod timer;
 
-pub use app::App;
-pub use commands::Commands;
+pub use app::{App, AppHandle};
+pub use command_error::{CommandError, CommandErrorHandler, CommandErrorKind};
+pub use commands::{Command, Commands, EntityCommands, ScheduledCommandHandle};
 pub use component::Component;
-pub use entities::{Entities, Entity};
+pub use condition::RunIf;
+pub use diagnostics::{SystemDiagnostics, SystemKey, SystemStats};
+pub use entities::{Disabled, Entities, Entity};
+pub use entity_clone::{CloneError, CloneOptions, NonCloneable};
+pub use executor::Executor;
+pub use hierarchy::{ChildBuilder, Children, Parent};
+pub use name::{EntityLabel, Name, Names};
+pub use pipe::PipeSystem;
 pub use query::{Query, Ref, RefMut};
 pub use query_parameters::QueryParameter;
+pub use relationship::{Relationship, RelationshipCleanup, RelationshipTarget};
 pub use resource::{Res, ResMut, Resource};
-pub use system::{IntoSystem, System, SystemFunction};
+pub use schedule::{FixedUpdate, ScheduleLabel, Update};
+pub use state::{
+    in_state, ComputedStates, InState, NextState, OnEnter, OnExit, OnTransition, State,
+    StateScoped, StateSet, States, SubStates,
+};
+pub use system::{In, IntoSystem, System, SystemFunction, SystemId};
 pub use system_parameters::SystemParameter;
-pub use system_set::SystemSet;
+pub use system_set::{SystemHandle, SystemSet};
+pub use time::{Clock, Fixed, ManualClock, Real, SystemClock, Time, Virtual};
+pub use timer::{tick_stopwatches, tick_timers, Stopwatch, Timer, TimerMode};
+
+#[doc(hidden)]
+pub mod __private {
+    pub use crate::component::{CloneFnFallback, CloneFnProbe, CloneFnSpecialized};
+}
 
 #[cfg(test)]
 mod tests {
-    use crate::{App, Commands, Component, Entities, Query, Ref, RefMut, SystemSet};
+    use crate::{command_queue::CommandQueue, Command};
+    use crate::{
+        App, Children, CloneError, CloneOptions, CommandError, CommandErrorHandler,
+        CommandErrorKind, Commands, Component, ComputedStates, Disabled, Entities, Entity,
+        Executor, Fixed, FixedUpdate, In, IntoSystem, ManualClock, Name, Names, NextState,
+        NonCloneable, OnEnter, OnExit, OnTransition, Parent, Query, Real, Ref, RefMut,
+        Relationship, RelationshipCleanup, RelationshipTarget, Res, ResMut, Resource, State,
+        StateScoped, States, Stopwatch, SubStates, SystemDiagnostics, SystemSet, Time, Timer,
+        TimerMode, Update, Virtual,
+    };
+    use parking_lot::Mutex;
+    use std::{sync::Arc, time::Duration};
 
     #[test]
     fn test() {
@@ -90,4 +136,1331 @@ mod tests {
             },
         );
     }
+
+    #[test]
+    fn optional_query_parameters() {
+        struct Position(i32);
+        impl Component for Position {}
+
+        struct Velocity(i32);
+        impl Component for Velocity {}
+
+        let mut app = App::new();
+        let moving = app.create_entity();
+        app.add_component(moving, Position(0));
+        app.add_component(moving, Velocity(1));
+        // the velocity storage ends before this entity
+        let still = app.create_entity();
+        app.add_component(still, Position(5));
+
+        app.run(
+            |mut query: Query<'_, (RefMut<'_, Position>, Option<Ref<'_, Velocity>>)>| {
+                for (_, (mut position, velocity)) in query.iter_mut() {
+                    position.0 += velocity.map_or(0, |velocity| velocity.0);
+                }
+            },
+        );
+        let positions = app.run(
+            |query: Query<'_, (Ref<'_, Position>, Option<Ref<'_, Velocity>>)>| {
+                query
+                    .iter()
+                    .map(|(entity, (position, velocity))| (entity, position.0, velocity.is_some()))
+                    .collect::<Vec<_>>()
+            },
+        );
+        assert_eq!(positions, [(moving, 1, true), (still, 5, false)]);
+    }
+
+    #[test]
+    fn pipe() {
+        struct Health(i32);
+        impl Component for Health {}
+
+        struct Threshold(i32);
+        impl Resource for Threshold {}
+
+        let mut app = App::new();
+        app.add_resource(Threshold(10));
+        for health in [5, 15, 20] {
+            let entity = app.create_entity();
+            app.add_component(entity, Health(health));
+        }
+
+        let count_healthy = |q: Query<'_, Ref<'_, Health>>, threshold: Res<'_, Threshold>| {
+            q.iter()
+                .filter(|(_, health)| health.0 > threshold.0)
+                .count()
+        };
+        app.run(count_healthy.pipe(|In(count): In<usize>| assert_eq!(count, 2)));
+
+        let mut set = SystemSet::new();
+        set.register_system(
+            (|| 3).pipe(|In(value): In<i32>, threshold: Res<'_, Threshold>| {
+                assert_eq!(value + threshold.0, 13);
+            }),
+        );
+        app.run(&mut set);
+    }
+
+    #[test]
+    fn run_output() {
+        struct Enemy;
+        impl Component for Enemy {}
+
+        let mut app = App::new();
+        app.run(|mut commands: Commands<'_>| {
+            for _ in 0..3 {
+                commands.create_entity(Enemy);
+            }
+        });
+
+        let count_enemies = |q: Query<'_, Ref<'_, Enemy>>| q.iter().count();
+        assert_eq!(app.run(count_enemies), 3);
+        assert_eq!(
+            app.run_with_input(
+                |In(extra): In<usize>, q: Query<'_, Ref<'_, Enemy>>| q.iter().count() + extra,
+                2,
+            ),
+            5
+        );
+
+        let mut set = SystemSet::with_output(count_enemies);
+        set.register_system(|mut commands: Commands<'_>| commands.create_entity(Enemy));
+        assert_eq!(app.run(&mut set), 3);
+        assert_eq!(app.run(&mut set), 4);
+    }
+
+    #[test]
+    fn registered_systems() {
+        struct Counter(usize);
+        impl Resource for Counter {}
+
+        let mut app = App::new();
+        app.add_resource(Counter(0));
+
+        let mut runs = 0;
+        let id = app.register_system(move |mut counter: ResMut<'_, Counter>| {
+            runs += 1;
+            counter.0 = runs;
+        });
+
+        assert!(app.run_system(id));
+        app.run(move |mut commands: Commands<'_>| commands.run_system(id));
+        assert_eq!(app.run(|counter: Res<'_, Counter>| counter.0), 2);
+
+        assert!(app.unregister_system(id));
+        assert!(!app.run_system(id));
+        assert_eq!(app.run(|counter: Res<'_, Counter>| counter.0), 2);
+    }
+
+    #[test]
+    fn system_set_handles() {
+        struct Counter(usize);
+        impl Resource for Counter {}
+
+        let mut app = App::new();
+        app.add_resource(Counter(0));
+
+        let mut set = SystemSet::new();
+        let increment = set.register_system(|mut counter: ResMut<'_, Counter>| counter.0 += 1);
+        let add_ten = set.register_system(|mut counter: ResMut<'_, Counter>| counter.0 += 10);
+
+        app.run(&mut set);
+        assert_eq!(app.run(|counter: Res<'_, Counter>| counter.0), 11);
+
+        assert!(set.set_enabled(add_ten, false));
+        assert_eq!(set.is_enabled(add_ten), Some(false));
+        app.run(&mut set);
+        assert_eq!(app.run(|counter: Res<'_, Counter>| counter.0), 12);
+
+        assert!(set.remove_system(increment));
+        assert!(!set.remove_system(increment));
+        assert!(set.set_enabled(add_ten, true));
+        app.run(&mut set);
+        assert_eq!(app.run(|counter: Res<'_, Counter>| counter.0), 22);
+    }
+
+    #[test]
+    fn diagnostics() {
+        fn count_entities(entities: Entities<'_>) -> usize {
+            entities.iter().count()
+        }
+
+        let mut app = App::new();
+        app.run(count_entities);
+        app.add_resource(SystemDiagnostics::with_window(2));
+
+        let mut set = SystemSet::new();
+        let piped = set.register_system(count_entities.pipe(|_: In<usize>| {}));
+        // closures defined in the same function share a name, but are still timed separately
+        let first = set.register_system(|_: Entities<'_>| {});
+        let second = set.register_system(|_: Entities<'_>| {});
+        for _ in 0..3 {
+            app.run(&mut set);
+        }
+        set.set_enabled(second, false);
+        app.run(&mut set);
+        app.run(count_entities);
+        app.run(count_entities);
+
+        let name = std::any::type_name_of_val(&count_entities);
+        let diagnostics = app.remove_resource::<SystemDiagnostics>().unwrap();
+        let run_count = |system| diagnostics.get(system).map(|stats| stats.run_count());
+        assert_eq!(run_count(piped), Some(4));
+        assert_eq!(run_count(first), Some(4));
+        assert_eq!(run_count(second), Some(3));
+        // the empty output system of the set is not timed
+        assert_eq!(diagnostics.iter().count(), 4);
+        assert_eq!(
+            diagnostics.get(first).unwrap().name(),
+            diagnostics.get(second).unwrap().name()
+        );
+        assert!(diagnostics
+            .get(piped)
+            .unwrap()
+            .name()
+            .starts_with(&format!("{name} | ")));
+        assert_eq!(
+            diagnostics.get_by_name(name).map(|stats| stats.run_count()),
+            Some(2)
+        );
+
+        assert!(diagnostics.table().contains(name));
+        assert!(diagnostics
+            .json()
+            .contains(&format!("\"name\":\"{name}\",\"runs\":2")));
+    }
+
+    #[test]
+    fn single_threaded_executor() {
+        struct Marker;
+        impl Resource for Marker {}
+
+        let mut app = App::new();
+        app.add_resource(Marker);
+        app.set_executor(Executor::single_threaded());
+
+        let order = Arc::new(Mutex::new(Vec::new()));
+        let mut set = SystemSet::new();
+        for i in 0..3 {
+            let order = order.clone();
+            if i == 2 {
+                set.register_system(move || order.lock().push(i));
+            } else {
+                set.register_system(move |_: ResMut<'_, Marker>| order.lock().push(i));
+            }
+        }
+        app.run(&mut set);
+        assert_eq!(*order.lock(), [0, 1, 2]);
+    }
+
+    #[cfg(feature = "tracing")]
+    #[test]
+    fn tracing_spans() {
+        use std::{
+            fmt::Write,
+            sync::atomic::{AtomicU64, Ordering},
+        };
+        use tracing::{
+            field::{Field, Visit},
+            span, Event, Metadata, Subscriber,
+        };
+
+        /// Records the name and fields of every span that is created
+        #[derive(Default)]
+        struct SpanRecorder {
+            next_id: AtomicU64,
+            spans: Mutex<Vec<String>>,
+        }
+        struct Fields(String);
+        impl Visit for Fields {
+            fn record_debug(&mut self, field: &Field, value: &dyn std::fmt::Debug) {
+                _ = write!(self.0, " {}={value:?}", field.name());
+            }
+        }
+        impl Subscriber for SpanRecorder {
+            fn enabled(&self, _: &Metadata<'_>) -> bool {
+                true
+            }
+            fn new_span(&self, span: &span::Attributes<'_>) -> span::Id {
+                let mut fields = Fields(span.metadata().name().to_string());
+                span.record(&mut fields);
+                self.spans.lock().push(fields.0);
+                span::Id::from_u64(self.next_id.fetch_add(1, Ordering::Relaxed) + 1)
+            }
+            fn record(&self, _: &span::Id, _: &span::Record<'_>) {}
+            fn record_follows_from(&self, _: &span::Id, _: &span::Id) {}
+            fn event(&self, _: &Event<'_>) {}
+            fn enter(&self, _: &span::Id) {}
+            fn exit(&self, _: &span::Id) {}
+        }
+
+        fn count_entities(entities: Entities<'_>) -> usize {
+            entities.iter().count()
+        }
+
+        let mut app = App::new();
+        // the subscriber is only set for this thread, so the systems must not run on a thread pool
+        app.set_executor(Executor::single_threaded());
+        app.add_system(Update, |_: Entities<'_>| {});
+        app.add_system(Update, count_entities.pipe(|_: In<usize>| {}));
+
+        let recorder = Arc::new(SpanRecorder::default());
+        tracing::subscriber::with_default(recorder.clone(), || app.update());
+        let spans = std::mem::take(&mut *recorder.spans.lock());
+
+        for span in [
+            "state_transitions",
+            "schedule label=Update",
+            "system_group index=0 systems=2",
+            "apply_commands",
+            "handle_commands",
+            "scheduled_commands",
+        ] {
+            assert!(
+                spans.iter().any(|recorded| recorded == span),
+                "{span} in {spans:?}"
+            );
+        }
+        let name = std::any::type_name_of_val(&count_entities);
+        assert!(
+            spans
+                .iter()
+                .any(|span| span == &format!("system name={name:?}")),
+            "{spans:?}"
+        );
+        // both halves of the piped system get their own span
+        assert_eq!(
+            spans
+                .iter()
+                .filter(
+                    |span| span.starts_with("system name=\"thallium_ecs::tests::tracing_spans::")
+                )
+                .count(),
+            3,
+            "{spans:?}"
+        );
+    }
+
+    #[test]
+    fn command_order() {
+        struct Index(usize);
+        impl Component for Index {}
+
+        let mut app = App::new();
+        let mut set = SystemSet::new();
+        for i in 0..32 {
+            set.register_system(move |mut commands: Commands<'_>| {
+                commands.create_entity(Index(i * 2));
+                commands.create_entity(Index(i * 2 + 1));
+            });
+        }
+        app.run(&mut set);
+
+        let indices = app.run(|q: Query<'_, Ref<'_, Index>>| {
+            q.iter().map(|(_, index)| index.0).collect::<Vec<_>>()
+        });
+        assert_eq!(indices, (0..64).collect::<Vec<_>>());
+    }
+
+    #[test]
+    fn command_queue() {
+        #[derive(Clone, Copy)]
+        struct Value(u64);
+        impl Component for Value {}
+        impl Command for Value {
+            fn apply(self, app: &mut App) {
+                let entity = app.create_entity();
+                app.add_component(entity, self);
+            }
+        }
+
+        let mut app = App::new();
+        let dropped = Arc::new(());
+        let mut queue = CommandQueue::new();
+        for _ in 0..2 {
+            let flag = 7u8;
+            queue.push(move |app: &mut App| {
+                let entity = app.create_entity();
+                app.add_component(entity, Value(flag as u64));
+            });
+            queue.push(Value(u64::MAX));
+            queue.push(|_: &mut App| {});
+            let dropped = dropped.clone();
+            queue.push(move |_: &mut App| drop(dropped));
+            queue.apply(&mut app);
+        }
+        assert_eq!(Arc::strong_count(&dropped), 1);
+
+        // a panicking command drops the commands queued after it instead of leaving them for the next apply
+        queue.push(|_: &mut App| panic!("command failed"));
+        queue.push(Value(0));
+        let panicked = std::panic::catch_unwind(std::panic::AssertUnwindSafe(|| {
+            queue.apply(&mut app);
+        }));
+        assert!(panicked.is_err());
+        queue.push(Value(1));
+        queue.apply(&mut app);
+
+        let dropped_later = dropped.clone();
+        queue.push(move |_: &mut App| drop(dropped_later));
+        drop(queue);
+        assert_eq!(Arc::strong_count(&dropped), 1);
+
+        let values = app.run(|q: Query<'_, Ref<'_, Value>>| {
+            q.iter().map(|(_, value)| value.0).collect::<Vec<_>>()
+        });
+        assert_eq!(values, [7, u64::MAX, 7, u64::MAX, 1]);
+    }
+
+    #[test]
+    fn entity_commands() {
+        #[derive(Default)]
+        struct Score(u32);
+        impl Resource for Score {}
+
+        struct Health(i32);
+        impl Component for Health {}
+
+        struct Shield;
+        impl Component for Shield {}
+
+        let mut app = App::new();
+        let entity = app.create_entity();
+        let destroyed = app.create_entity();
+        app.destroy_entity(destroyed);
+
+        app.run(move |mut commands: Commands<'_>| {
+            commands
+                .entity(entity)
+                .insert((Health(10), Shield))
+                .remove::<Shield>();
+            commands.entity(destroyed).try_insert(Health(5));
+            commands.init_resource::<Score>();
+            commands.insert_resource(Score(3));
+            commands.init_resource::<Score>();
+        });
+
+        let (health, shield) = app.run(
+            move |health: Query<'_, Ref<'_, Health>>, shield: Query<'_, Ref<'_, Shield>>| {
+                (
+                    health.get(entity).map(|h| h.0),
+                    shield.get(entity).is_some(),
+                )
+            },
+        );
+        assert_eq!((health, shield), (Some(10), false));
+        assert_eq!(app.run(|score: Res<'_, Score>| score.0), 3);
+
+        app.run(move |mut commands: Commands<'_>| {
+            commands.entity(entity).despawn();
+            commands.remove_resource::<Score>();
+        });
+        assert!(!app.entity_exists(entity));
+        assert!(!app.has_resource::<Score>());
+    }
+
+    #[test]
+    fn command_errors() {
+        static ERRORS: Mutex<Vec<CommandError>> = Mutex::new(Vec::new());
+
+        struct Health;
+        impl Component for Health {}
+
+        struct Shield;
+        impl Component for Shield {}
+
+        let mut app = App::new();
+        app.set_command_error_handler(CommandErrorHandler::Custom(|error, _| {
+            ERRORS.lock().push(error.clone())
+        }));
+        let destroyed = app.create_entity();
+        app.destroy_entity(destroyed);
+
+        app.add_component(destroyed, Health);
+        assert!(!app.insert_if_alive(destroyed, Health));
+        app.run(move |mut commands: Commands<'_>| {
+            commands.add_components(destroyed, (Health, Shield));
+            commands
+                .entity(destroyed)
+                .try_insert(Health)
+                .try_remove::<Shield>();
+            commands.entity(destroyed).remove::<Shield>().try_despawn();
+            commands.destroy_entity(destroyed);
+        });
+
+        let errors = std::mem::take(&mut *ERRORS.lock());
+        assert_eq!(
+            errors
+                .iter()
+                .map(|error| (error.kind, error.entity, error.components.len()))
+                .collect::<Vec<_>>(),
+            [
+                (CommandErrorKind::InsertComponents, destroyed, 1),
+                (CommandErrorKind::InsertComponents, destroyed, 2),
+                (CommandErrorKind::RemoveComponents, destroyed, 1),
+                (CommandErrorKind::DestroyEntity, destroyed, 0),
+            ]
+        );
+        assert!(errors[1].components[1].ends_with("Shield"));
+
+        app.set_command_error_handler(CommandErrorHandler::Panic);
+        let result = std::panic::catch_unwind(std::panic::AssertUnwindSafe(|| {
+            app.add_component(destroyed, Shield)
+        }));
+        assert!(result.is_err());
+    }
+
+    #[test]
+    fn scheduled_commands() {
+        #[derive(Default)]
+        struct Log(Vec<&'static str>);
+        impl Resource for Log {}
+
+        let mut app = App::new();
+        app.init_resource::<Log>();
+        let entity = app.create_entity();
+
+        let cancelled = app.run(move |mut commands: Commands<'_>| {
+            commands.schedule_after(2, |app: &mut App| {
+                app.run(|mut log: ResMut<'_, Log>| log.0.push("after 2"))
+            });
+            commands.schedule_at(1, |app: &mut App| {
+                app.run(|mut log: ResMut<'_, Log>| log.0.push("at 1"))
+            });
+            commands.entity(entity).schedule_after(1, |app, _| {
+                app.run(|mut log: ResMut<'_, Log>| log.0.push("entity"))
+            });
+            commands.schedule_after(1, |app: &mut App| {
+                app.run(|mut log: ResMut<'_, Log>| log.0.push("cancelled"))
+            })
+        });
+        cancelled.cancel();
+        app.destroy_entity(entity);
+
+        let log = |app: &mut App| app.run(|log: Res<'_, Log>| log.0.clone());
+        assert!(log(&mut app).is_empty());
+        app.next_tick();
+        assert_eq!(log(&mut app), ["at 1"]);
+        app.next_tick();
+        assert_eq!(log(&mut app), ["at 1", "after 2"]);
+    }
+
+    #[test]
+    fn app_handle() {
+        #[derive(Default)]
+        struct Messages(Vec<String>);
+        impl Resource for Messages {}
+
+        let mut app = App::new();
+        app.init_resource::<Messages>();
+
+        let handle = app.handle();
+        std::thread::scope(|scope| {
+            for i in 0..4 {
+                let handle = handle.clone();
+                scope.spawn(move || {
+                    handle.commands().schedule(move |app: &mut App| {
+                        app.run(move |mut messages: ResMut<'_, Messages>| {
+                            messages.0.push(format!("message {i}"))
+                        })
+                    });
+                });
+            }
+        });
+
+        let count = |app: &mut App| app.run(|messages: Res<'_, Messages>| messages.0.len());
+        assert_eq!(count(&mut app), 0);
+        app.next_tick();
+        assert_eq!(count(&mut app), 4);
+    }
+
+    #[test]
+    fn fixed_update() {
+        #[derive(Default)]
+        struct Counts {
+            fixed: u32,
+            update: u32,
+        }
+        impl Resource for Counts {}
+
+        let clock = ManualClock::new();
+        let mut app = App::new();
+        app.set_clock(clock.clone());
+        app.init_resource::<Counts>();
+        app.add_resource({
+            let mut fixed_time = Time::<Fixed>::from_duration(Duration::from_millis(10));
+            fixed_time.set_max_steps(3);
+            fixed_time
+        });
+        app.add_system(FixedUpdate, |mut counts: ResMut<'_, Counts>| {
+            counts.fixed += 1
+        });
+        app.add_system(Update, |mut counts: ResMut<'_, Counts>| counts.update += 1);
+
+        let step = |app: &mut App, millis| {
+            clock.advance(Duration::from_millis(millis));
+            app.update();
+            app.run(
+                |counts: Res<'_, Counts>, fixed_time: Res<'_, Time<Fixed>>| {
+                    (counts.fixed, counts.update, fixed_time.alpha())
+                },
+            )
+        };
+        assert_eq!(step(&mut app, 0), (0, 1, 0.0));
+        assert_eq!(step(&mut app, 25), (2, 2, 0.5));
+        // only 3 steps are run, the rest are dropped
+        assert_eq!(step(&mut app, 100), (5, 3, 0.5));
+        assert_eq!(step(&mut app, 5), (6, 4, 0.0));
+    }
+
+    #[test]
+    fn time() {
+        let clock = ManualClock::new();
+        let mut app = App::new();
+        app.set_clock(clock.clone());
+        // the time resources are there before the first update
+        app.run(|mut fixed: ResMut<'_, Time<Fixed>>| fixed.set_timestep(Duration::from_millis(10)));
+        app.add_system(FixedUpdate, |time: Res<'_, Time>| {
+            assert_eq!(time.delta(), Duration::from_millis(10))
+        });
+
+        let step = |app: &mut App, millis| {
+            clock.advance(Duration::from_millis(millis));
+            app.update();
+            app.run(
+                |real: Res<'_, Time<Real>>,
+                 virtual_time: Res<'_, Time<Virtual>>,
+                 fixed: Res<'_, Time<Fixed>>,
+                 time: Res<'_, Time>| {
+                    assert_eq!(time.elapsed(), virtual_time.elapsed());
+                    (real.elapsed(), virtual_time.elapsed(), fixed.elapsed())
+                },
+            )
+        };
+        let ms = Duration::from_millis;
+
+        assert_eq!(step(&mut app, 0), (ms(0), ms(0), ms(0)));
+        assert_eq!(step(&mut app, 20), (ms(20), ms(20), ms(20)));
+
+        app.run(|mut time: ResMut<'_, Time<Virtual>>| time.pause());
+        assert_eq!(step(&mut app, 20), (ms(40), ms(20), ms(20)));
+
+        app.run(|mut time: ResMut<'_, Time<Virtual>>| {
+            time.unpause();
+            time.set_relative_speed(2.0);
+            time.set_max_delta(ms(30));
+        });
+        // the 50ms is clamped to 30ms then doubled, and only 5 of the 6 fixed steps are run
+        assert_eq!(step(&mut app, 50), (ms(90), ms(80), ms(70)));
+    }
+
+    #[test]
+    fn timers() {
+        let mut timer = Timer::new(Duration::from_millis(10), TimerMode::Repeating);
+        timer.tick(Duration::from_millis(25));
+        assert_eq!(timer.times_finished_this_tick(), 2);
+        assert_eq!(timer.fraction(), 0.5);
+        timer.tick(Duration::from_millis(1));
+        assert!(!timer.just_finished());
+
+        let clock = ManualClock::new();
+        let mut app = App::new();
+        app.set_clock(clock.clone());
+        app.add_system(Update, crate::tick_timers);
+        app.add_system(Update, crate::tick_stopwatches);
+
+        let entity = app.create_entity();
+        app.add_component(entity, Timer::from_seconds(0.02, TimerMode::Once));
+        app.add_component(entity, Stopwatch::new());
+
+        let step = |app: &mut App| {
+            clock.advance(Duration::from_millis(15));
+            app.update();
+            app.run(
+                move |timers: Query<'_, Ref<'_, Timer>>,
+                      stopwatches: Query<'_, Ref<'_, Stopwatch>>| {
+                    let timer = timers.get(entity).unwrap();
+                    (
+                        timer.just_finished(),
+                        timer.finished(),
+                        stopwatches.get(entity).unwrap().elapsed(),
+                    )
+                },
+            )
+        };
+        // the first update has no delta
+        step(&mut app);
+        assert_eq!(step(&mut app), (false, false, Duration::from_millis(15)));
+        assert_eq!(step(&mut app), (true, true, Duration::from_millis(30)));
+        assert_eq!(step(&mut app), (false, true, Duration::from_millis(45)));
+    }
+
+    #[test]
+    fn states() {
+        #[derive(Debug, Clone, PartialEq, Eq, Hash, Default)]
+        enum GameState {
+            #[default]
+            Menu,
+            InGame,
+        }
+        impl States for GameState {}
+
+        #[derive(Default)]
+        struct Log(Vec<String>);
+        impl Resource for Log {}
+
+        let mut app = App::new();
+        app.init_resource::<Log>();
+        app.init_state::<GameState>();
+
+        for state in [GameState::Menu, GameState::InGame] {
+            let enter = format!("enter {state:?}");
+            app.add_system(OnEnter(state.clone()), move |mut log: ResMut<'_, Log>| {
+                log.0.push(enter.clone())
+            });
+            let exit = format!("exit {state:?}");
+            app.add_system(OnExit(state), move |mut log: ResMut<'_, Log>| {
+                log.0.push(exit.clone())
+            });
+        }
+        app.add_system(
+            OnTransition {
+                exited: GameState::Menu,
+                entered: GameState::InGame,
+            },
+            |mut log: ResMut<'_, Log>| log.0.push("start game".into()),
+        );
+        app.add_system(
+            Update,
+            (|mut log: ResMut<'_, Log>| log.0.push("update in game".into()))
+                .run_if(crate::in_state(GameState::InGame)),
+        );
+        app.add_system(OnEnter(GameState::Menu), |mut commands: Commands<'_>| {
+            commands.create_entity(StateScoped(GameState::Menu));
+            // disabled entities are cleaned up as well
+            commands.create_entity((StateScoped(GameState::Menu), Disabled));
+        });
+
+        let take_log =
+            |app: &mut App| app.run(|mut log: ResMut<'_, Log>| std::mem::take(&mut log.0));
+        let scoped_count = |app: &mut App| {
+            app.run(|q: Query<'_, Ref<'_, StateScoped<GameState>>>| {
+                q.including_disabled().iter().count()
+            })
+        };
+
+        app.update();
+        assert_eq!(take_log(&mut app), ["enter Menu"]);
+        assert_eq!(scoped_count(&mut app), 2);
+
+        app.run(|mut next: ResMut<'_, NextState<GameState>>| next.set(GameState::InGame));
+        app.update();
+        assert_eq!(
+            take_log(&mut app),
+            ["exit Menu", "start game", "enter InGame", "update in game"]
+        );
+        assert_eq!(scoped_count(&mut app), 0);
+        assert_eq!(
+            app.run(|state: Res<'_, State<GameState>>| state.get().clone()),
+            GameState::InGame
+        );
+    }
+
+    #[test]
+    fn sub_and_computed_states() {
+        #[derive(Debug, Clone, PartialEq, Eq, Hash, Default)]
+        enum GameState {
+            #[default]
+            Menu,
+            InGame,
+        }
+        impl States for GameState {}
+
+        #[derive(Debug, Clone, PartialEq, Eq, Hash)]
+        enum PauseMenu {
+            Closed,
+            Open,
+        }
+        impl States for PauseMenu {}
+        impl SubStates for PauseMenu {
+            type SourceStates = GameState;
+
+            fn should_exist(game_state: GameState) -> Option<Self> {
+                (game_state == GameState::InGame).then_some(PauseMenu::Closed)
+            }
+        }
+
+        #[derive(Debug, Clone, PartialEq, Eq, Hash)]
+        struct Playing;
+        impl States for Playing {}
+        impl ComputedStates for Playing {
+            type SourceStates = (GameState, PauseMenu);
+
+            fn compute((game_state, pause_menu): (GameState, PauseMenu)) -> Option<Self> {
+                (game_state == GameState::InGame && pause_menu == PauseMenu::Closed)
+                    .then_some(Playing)
+            }
+        }
+
+        #[derive(Default)]
+        struct Log(Vec<String>);
+        impl Resource for Log {}
+
+        fn log_transitions<S: States>(app: &mut App, state: S) {
+            let enter = format!("enter {state:?}");
+            app.add_system(OnEnter(state.clone()), move |mut log: ResMut<'_, Log>| {
+                log.0.push(enter.clone())
+            });
+            let exit = format!("exit {state:?}");
+            app.add_system(OnExit(state), move |mut log: ResMut<'_, Log>| {
+                log.0.push(exit.clone())
+            });
+        }
+
+        let mut app = App::new();
+        app.init_resource::<Log>();
+        app.init_state::<GameState>();
+        app.add_sub_state::<PauseMenu>();
+        app.add_computed_state::<Playing>();
+        log_transitions(&mut app, GameState::Menu);
+        log_transitions(&mut app, GameState::InGame);
+        log_transitions(&mut app, PauseMenu::Closed);
+        log_transitions(&mut app, PauseMenu::Open);
+        log_transitions(&mut app, Playing);
+
+        let take_log =
+            |app: &mut App| app.run(|mut log: ResMut<'_, Log>| std::mem::take(&mut log.0));
+
+        app.update();
+        assert_eq!(take_log(&mut app), ["enter Menu"]);
+
+        app.run(|mut next: ResMut<'_, NextState<GameState>>| next.set(GameState::InGame));
+        app.update();
+        assert_eq!(
+            take_log(&mut app),
+            ["exit Menu", "enter InGame", "enter Closed", "enter Playing"]
+        );
+
+        app.run(|mut next: ResMut<'_, NextState<PauseMenu>>| next.set(PauseMenu::Open));
+        app.update();
+        assert_eq!(
+            take_log(&mut app),
+            ["exit Playing", "exit Closed", "enter Open"]
+        );
+
+        app.run(|mut next: ResMut<'_, NextState<GameState>>| next.set(GameState::Menu));
+        app.update();
+        assert_eq!(
+            take_log(&mut app),
+            ["exit Open", "exit InGame", "enter Menu"]
+        );
+        assert!(!app.has_resource::<State<PauseMenu>>());
+    }
+
+    #[test]
+    fn hierarchy() {
+        struct Turret;
+        impl Component for Turret {}
+
+        let mut app = App::new();
+        let ship = app.create_entity();
+        let other_ship = app.create_entity();
+
+        app.run(move |mut commands: Commands<'_>| {
+            commands.entity(ship).with_children(|children| {
+                children.spawn(Turret).spawn(Turret);
+            });
+        });
+        let turrets = app.run(move |children: Query<'_, Ref<'_, Children>>| {
+            children.get(ship).unwrap().to_vec()
+        });
+        assert_eq!(turrets.len(), 2);
+        let gunner = app.create_entity();
+        app.set_parent(gunner, turrets[0]);
+
+        let (breadth_first, depth_first, ancestors) = app.run(
+            move |children: Query<'_, Ref<'_, Children>>, parents: Query<'_, Ref<'_, Parent>>| {
+                (
+                    children.iter_descendants(ship).collect::<Vec<_>>(),
+                    children
+                        .iter_descendants_depth_first(ship)
+                        .collect::<Vec<_>>(),
+                    parents.iter_ancestors(gunner).collect::<Vec<_>>(),
+                )
+            },
+        );
+        assert_eq!(breadth_first, [turrets[0], turrets[1], gunner]);
+        assert_eq!(depth_first, [turrets[0], gunner, turrets[1]]);
+        assert_eq!(ancestors, [turrets[0], ship]);
+
+        // disabled entities are walked through but not returned
+        app.add_component(turrets[0], Disabled);
+        let (breadth_first, ancestors) = app.run(
+            move |children: Query<'_, Ref<'_, Children>>, parents: Query<'_, Ref<'_, Parent>>| {
+                (
+                    children.iter_descendants(ship).collect::<Vec<_>>(),
+                    parents.iter_ancestors(gunner).collect::<Vec<_>>(),
+                )
+            },
+        );
+        assert_eq!(breadth_first, [turrets[1], gunner]);
+        assert_eq!(ancestors, [ship]);
+        app.remove_component::<Disabled>(turrets[0]);
+
+        // moving a turret removes it from the children of its old parent
+        app.add_child(other_ship, turrets[1]);
+        let children_of = |app: &mut App, entity| {
+            app.run(move |children: Query<'_, Ref<'_, Children>>| {
+                children.get(entity).map(|children| children.to_vec())
+            })
+        };
+        assert_eq!(children_of(&mut app, ship), Some(vec![turrets[0]]));
+        assert_eq!(children_of(&mut app, other_ship), Some(vec![turrets[1]]));
+
+        // destroying an entity leaves no dangling references behind
+        app.destroy_entity(turrets[0]);
+        assert_eq!(children_of(&mut app, ship), None);
+        assert!(app.run(move |parents: Query<'_, Ref<'_, Parent>>| parents.get(gunner).is_none()));
+
+        app.run(move |mut commands: Commands<'_>| {
+            commands.entity(other_ship).despawn_recursive();
+        });
+        assert!(!app.entity_exists(other_ship));
+        assert!(!app.entity_exists(turrets[1]));
+        assert!(app.entity_exists(gunner));
+    }
+
+    #[test]
+    fn relationships() {
+        macro_rules! relationship {
+            ($source:ident, $target:ident, $cleanup:ident) => {
+                struct $source(Entity);
+                impl Component for $source {}
+                impl Relationship for $source {
+                    type Target = $target;
+                    const CLEANUP: RelationshipCleanup = RelationshipCleanup::$cleanup;
+                    fn from_target(target: Entity) -> Self {
+                        Self(target)
+                    }
+                    fn target(&self) -> Entity {
+                        self.0
+                    }
+                }
+
+                struct $target(Vec<Entity>);
+                impl Component for $target {}
+                impl RelationshipTarget for $target {
+                    type Relationship = $source;
+                    fn from_sources(sources: Vec<Entity>) -> Self {
+                        Self(sources)
+                    }
+                    fn sources(&self) -> &[Entity] {
+                        &self.0
+                    }
+                    fn sources_mut(&mut self) -> &mut Vec<Entity> {
+                        &mut self.0
+                    }
+                }
+            };
+        }
+        relationship!(DockedAt, Docked, RemoveRelation);
+        relationship!(OwnedBy, Owned, DespawnSource);
+        relationship!(Targets, TargetedBy, DespawnTarget);
+
+        fn sources<T: RelationshipTarget>(app: &mut App, entity: Entity) -> Option<Vec<Entity>> {
+            app.run(move |targets: Query<'_, Ref<'_, T>>| {
+                targets
+                    .get(entity)
+                    .map(|targets| targets.sources().to_vec())
+            })
+        }
+
+        let mut app = App::new();
+        let station = app.create_entity();
+        let other_station = app.create_entity();
+        let ship = app.create_entity();
+        let cargo = app.create_entity();
+        let marker = app.create_entity();
+
+        app.run(move |mut commands: Commands<'_>| {
+            commands.entity(ship).relate::<DockedAt>(station);
+            commands.relate::<OwnedBy>(cargo, ship);
+            commands.relate::<Targets>(marker, ship);
+        });
+        assert_eq!(sources::<Docked>(&mut app, station), Some(vec![ship]));
+        assert_eq!(sources::<Owned>(&mut app, ship), Some(vec![cargo]));
+
+        // relating to a new target moves the source out of the old target
+        app.relate::<DockedAt>(ship, other_station);
+        assert_eq!(sources::<Docked>(&mut app, station), None);
+        assert_eq!(sources::<Docked>(&mut app, other_station), Some(vec![ship]));
+
+        // removing the relation directly cleans up the target too
+        assert_eq!(app.unrelate::<DockedAt>(ship), Some(other_station));
+        assert_eq!(sources::<Docked>(&mut app, other_station), None);
+        app.relate::<DockedAt>(ship, station);
+
+        // destroying the target of a `RemoveRelation` relationship leaves the source alive
+        app.destroy_entity(station);
+        assert!(app.entity_exists(ship));
+        assert!(app.run(move |docked: Query<'_, Ref<'_, DockedAt>>| docked.get(ship).is_none()));
+
+        // destroying the source of a `DespawnTarget` relationship destroys the target,
+        // which destroys the sources of its `DespawnSource` relationships
+        app.destroy_entity(marker);
+        assert!(!app.entity_exists(ship));
+        assert!(!app.entity_exists(cargo));
+
+        app.set_command_error_handler(CommandErrorHandler::Panic);
+        let result = std::panic::catch_unwind(std::panic::AssertUnwindSafe(|| {
+            app.relate::<OwnedBy>(other_station, ship);
+        }));
+        assert!(result.is_err());
+    }
+
+    #[test]
+    fn entity_cloning() {
+        #[derive(Clone)]
+        struct Health(i32);
+        impl Component for Health {
+            fn clone_fn() -> Option<fn(&Self) -> Self> {
+                Some(Self::clone)
+            }
+        }
+        #[derive(Clone)]
+        struct Armor(u32);
+        impl Component for Armor {}
+        struct Handle;
+        impl Component for Handle {}
+
+        let mut app = App::new();
+        app.register_component_clone::<Armor>();
+        let ship = app.create_entity();
+        app.add_component(ship, Health(10));
+        app.add_component(ship, Armor(3));
+        let turret = app.create_entity();
+        app.add_component(turret, Health(5));
+        app.add_component(turret, Handle);
+        app.set_parent(turret, ship);
+
+        let stats = |app: &mut App, entity| {
+            app.run(
+                move |health: Query<'_, Ref<'_, Health>>, armor: Query<'_, Ref<'_, Armor>>| {
+                    (
+                        health.get(entity).map(|health| health.0),
+                        armor.get(entity).map(|armor| armor.0),
+                    )
+                },
+            )
+        };
+
+        let clone = app.clone_entity(ship);
+        assert_eq!(stats(&mut app, clone), (Some(10), Some(3)));
+        assert!(
+            app.run(move |children: Query<'_, Ref<'_, Children>>| children.get(clone).is_none())
+        );
+
+        // cloning a child keeps its parent, but leaves off components that cannot be cloned
+        let turret_clone = app.clone_entity(turret);
+        let (parent, handle) = app.run(
+            move |parents: Query<'_, Ref<'_, Parent>>, handles: Query<'_, Ref<'_, Handle>>| {
+                (
+                    parents.get(turret_clone).map(|parent| parent.get()),
+                    handles.get(turret_clone).is_some(),
+                )
+            },
+        );
+        assert_eq!(parent, Some(ship));
+        assert!(!handle);
+        app.destroy_entity(turret_clone);
+
+        let options = CloneOptions {
+            non_cloneable: NonCloneable::Error,
+            recursive: true,
+        };
+        let entity_count =
+            |app: &mut App| app.run(|entities: Entities<'_>| entities.iter().count());
+        let count = entity_count(&mut app);
+        assert_eq!(
+            app.clone_entity_with(ship, options),
+            Err(CloneError::NotCloneable {
+                entity: turret,
+                name: None,
+                component: std::any::type_name::<Handle>(),
+            })
+        );
+        assert_eq!(entity_count(&mut app), count);
+
+        app.remove_component::<Handle>(turret);
+        let fleet = app.clone_entity_with(ship, options).unwrap();
+        let turrets = app.run(move |children: Query<'_, Ref<'_, Children>>| {
+            children.get(fleet).unwrap().to_vec()
+        });
+        assert_eq!(turrets.len(), 1);
+        assert_ne!(turrets[0], turret);
+        assert_eq!(stats(&mut app, turrets[0]), (Some(5), None));
+
+        // the clone only exists once the commands are applied, so it is passed to a callback
+        let spawned = Arc::new(Mutex::new(None));
+        let spawned_clone = spawned.clone();
+        app.run(move |mut commands: Commands<'_>| {
+            let spawned = spawned_clone.clone();
+            commands
+                .entity(turret)
+                .clone_and_spawn(move |_, clone| *spawned.lock() = Some(clone));
+        });
+        assert_eq!(entity_count(&mut app), count + 3);
+        let spawned = spawned.lock().unwrap();
+        assert_ne!(spawned, turret);
+        assert_eq!(stats(&mut app, spawned), (Some(5), None));
+    }
+
+    #[test]
+    fn app_try_clone() {
+        #[derive(Clone)]
+        struct Score(u32);
+        impl Resource for Score {
+            fn resource_clone_fn() -> Option<fn(&Self) -> Self> {
+                Some(Self::clone)
+            }
+        }
+        #[derive(Clone)]
+        struct Health(i32);
+        impl Component for Health {
+            fn clone_fn() -> Option<fn(&Self) -> Self> {
+                Some(Self::clone)
+            }
+        }
+        struct Handle;
+        impl Component for Handle {}
+        struct Connection;
+        impl Resource for Connection {}
+
+        fn take_damage(mut score: ResMut<'_, Score>, mut healths: Query<'_, RefMut<'_, Health>>) {
+            for (_, mut health) in healths.iter_mut() {
+                health.0 -= 1;
+                score.0 += 1;
+            }
+        }
+
+        let mut app = App::new();
+        app.add_resource(Score(0));
+        app.add_system(Update, take_damage);
+        let ship = app.create_entity();
+        app.add_component(ship, Health(10));
+        let turret = app.create_entity();
+        app.add_component(turret, Health(5));
+        app.add_component(turret, Handle);
+        app.set_parent(turret, ship);
+        app.update();
+
+        assert_eq!(
+            app.try_clone().err(),
+            Some(CloneError::NotCloneable {
+                entity: turret,
+                name: None,
+                component: std::any::type_name::<Handle>(),
+            })
+        );
+        app.remove_component::<Handle>(turret);
+        app.add_resource(Connection);
+        assert_eq!(
+            app.try_clone().err(),
+            Some(CloneError::ResourceNotCloneable {
+                resource: std::any::type_name::<Connection>(),
+            })
+        );
+        app.remove_resource::<Connection>();
+        // a resource missing from the clone registry is reported instead of panicking
+        let score_clone = app
+            .resource_clones
+            .remove(&std::any::TypeId::of::<Score>())
+            .unwrap();
+        assert_eq!(
+            app.try_clone().err(),
+            Some(CloneError::ResourceNotCloneable {
+                resource: std::any::type_name::<Score>(),
+            })
+        );
+        app.resource_clones
+            .insert(std::any::TypeId::of::<Score>(), score_clone);
+
+        // the fork has the same entities but has to have its systems added again
+        let mut fork = app.try_clone().unwrap();
+        fork.add_system(Update, take_damage);
+        for _ in 0..3 {
+            fork.update();
+        }
+
+        let state = |app: &mut App| {
+            app.run(
+                move |score: Res<'_, Score>,
+                      healths: Query<'_, Ref<'_, Health>>,
+                      children: Query<'_, Ref<'_, Children>>| {
+                    (
+                        score.0,
+                        healths.get(turret).map(|health| health.0),
+                        children.get(ship).map(|children| children.to_vec()),
+                    )
+                },
+            )
+        };
+        assert_eq!(state(&mut fork), (8, Some(1), Some(vec![turret])));
+        assert_eq!(state(&mut app), (2, Some(4), Some(vec![turret])));
+
+        // relationships keep working in the fork
+        fork.destroy_entity(turret);
+        assert_eq!(state(&mut fork).2, None);
+        assert!(app.entity_exists(turret));
+
+        // pending state transitions are copied too, so the fork still runs OnEnter
+        #[derive(Debug, Clone, PartialEq, Eq, Hash)]
+        struct Playing;
+        impl States for Playing {}
+        let entered = |mut score: ResMut<'_, Score>| score.0 += 100;
+        app.insert_state(Playing);
+        let mut fork = app.try_clone().unwrap();
+        fork.add_system(OnEnter(Playing), entered);
+        fork.update();
+        assert_eq!(fork.run(|score: Res<'_, Score>| score.0), 102);
+    }
+
+    #[test]
+    fn disabled_entities() {
+        struct Bullet;
+        impl Component for Bullet {}
+
+        let mut app = App::new();
+        let bullets = [(); 3].map(|()| {
+            let bullet = app.create_entity();
+            app.add_component(bullet, Bullet);
+            bullet
+        });
+        app.add_component(bullets[1], Disabled);
+
+        let visible = app.run(
+            move |bullets_query: Query<'_, Ref<'_, Bullet>>, entities: Entities<'_>| {
+                (
+                    bullets_query
+                        .iter()
+                        .map(|(entity, _)| entity)
+                        .collect::<Vec<_>>(),
+                    bullets_query.get(bullets[1]).is_some(),
+                    entities.iter().count(),
+                )
+            },
+        );
+        assert_eq!(visible, (vec![bullets[0], bullets[2]], false, 2));
+
+        // the pool manager opts in to seeing disabled bullets
+        let disabled = app.run(
+            move |disabled: Query<'_, (Ref<'_, Bullet>, Ref<'_, Disabled>)>| {
+                disabled
+                    .iter()
+                    .map(|(entity, _)| entity)
+                    .collect::<Vec<_>>()
+            },
+        );
+        assert_eq!(disabled, [bullets[1]]);
+        let pooled = app.run(
+            move |all: Query<'_, RefMut<'_, Bullet>>, entities: Entities<'_>| {
+                let mut all = all.including_disabled();
+                (
+                    all.iter_mut().count(),
+                    all.get_mut(bullets[1]).is_some(),
+                    entities.including_disabled().iter().count(),
+                )
+            },
+        );
+        assert_eq!(pooled, (3, true, 3));
+
+        app.run(move |mut commands: Commands<'_>| {
+            commands.entity(bullets[1]).remove::<Disabled>();
+            commands.entity(bullets[2]).insert(Disabled);
+        });
+        let visible = app.run(move |bullets_query: Query<'_, Ref<'_, Bullet>>| {
+            bullets_query
+                .iter()
+                .map(|(entity, _)| entity)
+                .collect::<Vec<_>>()
+        });
+        assert_eq!(visible, [bullets[0], bullets[1]]);
+    }
+
+    #[test]
+    fn names() {
+        static ERRORS: Mutex<Vec<String>> = Mutex::new(Vec::new());
+
+        let mut app = App::new();
+        app.set_command_error_handler(CommandErrorHandler::Custom(|error, label| {
+            ERRORS.lock().push(error.with_label(label).to_string())
+        }));
+        let ship = app.create_entity();
+        app.add_component(ship, Name::new("PlayerShip"));
+        let drones = [(); 2].map(|()| {
+            let drone = app.create_entity();
+            app.add_component(drone, Name::new(String::from("Drone")));
+            drone
+        });
+
+        assert_eq!(format!("{:?}", drones[0]), "Entity(1v1)");
+        assert_eq!(
+            format!("{:?}", app.entity_label(ship)),
+            r#"Entity(0v1 "PlayerShip")"#
+        );
+        assert_eq!(
+            app.entity_label(drones[1]).to_string(),
+            r#"Entity(2v1 "Drone")"#
+        );
+        let lookup = |app: &mut App| {
+            app.run(|names: Res<'_, Names>| {
+                (
+                    names.get("PlayerShip"),
+                    names.get_all("Drone").to_vec(),
+                    names.get("Scout"),
+                )
+            })
+        };
+        assert_eq!(lookup(&mut app), (Some(ship), drones.to_vec(), None));
+
+        // renaming and cloning keep the lookup in sync
+        app.add_component(drones[0], Name::new("Scout"));
+        let clone = app.clone_entity(drones[1]);
+        assert_eq!(
+            lookup(&mut app),
+            (Some(ship), vec![drones[1], clone], Some(drones[0]))
+        );
+
+        // names changed in place by a system are picked up at the end of the update
+        app.update();
+        app.add_system(Update, move |mut names: Query<'_, RefMut<'_, Name>>| {
+            *names.get_mut(drones[0]).unwrap() = Name::new("Interceptor");
+        });
+        app.update();
+        assert_eq!(
+            app.run(|names: Res<'_, Names>| (names.get("Scout"), names.get("Interceptor"))),
+            (None, Some(drones[0]))
+        );
+        assert_eq!(
+            app.entity_label(drones[0]).to_string(),
+            r#"Entity(1v1 "Interceptor")"#
+        );
+
+        // destroyed entities can no longer be found, but errors about them still show their name
+        app.destroy_entity(ship);
+        assert_eq!(lookup(&mut app).0, None);
+        app.add_component(ship, Name::new("Wreck"));
+        assert_eq!(
+            std::mem::take(&mut *ERRORS.lock()),
+            [format!(
+                r#"insert components command failed for Entity(0v1 "PlayerShip") with components [{}], the entity does not exist"#,
+                std::any::type_name::<Name>()
+            )]
+        );
+
+        // the old name is not shown once the id is reused
+        let reused = app.create_entity();
+        assert_eq!(format!("{:?}", app.entity_label(reused)), "Entity(0v2)");
+    }
 }
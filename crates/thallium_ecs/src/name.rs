@@ -0,0 +1,229 @@
+use crate::{
+    relationship::ComponentHooks, App, Component, Entity, IntoSystem, Query, Ref, ResMut, Resource,
+};
+use std::{
+    any::TypeId,
+    borrow::{Borrow, Cow},
+    collections::HashMap,
+    fmt,
+    ops::Deref,
+};
+
+/// A [`Component`] that gives an [`Entity`] a name, which is shown whenever the [`Entity`] is printed through an [`EntityLabel`] and can be looked up with [`Names`]
+///
+/// Inserting a new [`Name`] updates [`Names`] straight away, while changes made through a [`RefMut`](crate::RefMut) are picked up by the next [`App::next_tick`]
+#[derive(Debug, Clone, PartialEq, Eq, PartialOrd, Ord, Hash)]
+pub struct Name(Cow<'static, str>);
+
+impl Name {
+    /// Constructs a [`Name`]
+    pub fn new(name: impl Into<Cow<'static, str>>) -> Self {
+        Self(name.into())
+    }
+
+    /// Returns the name as a [`str`]
+    pub fn as_str(&self) -> &str {
+        &self.0
+    }
+}
+
+impl Component for Name {
+    fn clone_fn() -> Option<fn(&Self) -> Self> {
+        Some(Self::clone)
+    }
+}
+
+impl Deref for Name {
+    type Target = str;
+
+    fn deref(&self) -> &Self::Target {
+        &self.0
+    }
+}
+
+impl AsRef<str> for Name {
+    fn as_ref(&self) -> &str {
+        &self.0
+    }
+}
+
+impl Borrow<str> for Name {
+    fn borrow(&self) -> &str {
+        &self.0
+    }
+}
+
+impl fmt::Display for Name {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        f.write_str(&self.0)
+    }
+}
+
+impl From<&'static str> for Name {
+    fn from(name: &'static str) -> Self {
+        Self::new(name)
+    }
+}
+
+impl From<String> for Name {
+    fn from(name: String) -> Self {
+        Self::new(name)
+    }
+}
+
+/// A [`Resource`] that finds [`Entity`]s by their [`Name`], this is added by [`App::new`] and kept in sync whenever a [`Name`] is added, changed, or removed
+///
+/// Destroyed [`Entity`]s can no longer be looked up, but they keep their [`Name`] for [`EntityLabel`]s until another [`Entity`] with the same id is named
+///
+/// Picking up [`Name`]s changed in place goes through every [`Name`], but only on ticks where something borrowed them mutably, such as a system with a `Query<RefMut<Name>>`
+#[derive(Debug, Clone, Default)]
+pub struct Names {
+    entities: HashMap<Name, Vec<Entity>>,
+    labels: HashMap<usize, (Entity, Name)>,
+}
+
+impl Names {
+    /// Returns the first [`Entity`] that was given `name`
+    pub fn get(&self, name: &str) -> Option<Entity> {
+        self.get_all(name).first().copied()
+    }
+
+    /// Returns every [`Entity`] named `name` in the order they were named
+    pub fn get_all(&self, name: &str) -> &[Entity] {
+        self.entities.get(name).map_or(&[], Vec::as_slice)
+    }
+
+    /// Returns the [`Name`] of an [`Entity`], this includes destroyed [`Entity`]s
+    pub fn name(&self, entity: Entity) -> Option<&Name> {
+        self.labels
+            .get(&entity.id)
+            .filter(|&&(named, _)| named == entity)
+            .map(|(_, name)| name)
+    }
+
+    fn insert(&mut self, entity: Entity, name: Name) {
+        self.entities.entry(name.clone()).or_default().push(entity);
+        self.labels.insert(entity.id, (entity, name));
+    }
+
+    fn remove(&mut self, entity: Entity, name: &Name, destroyed: bool) {
+        if let Some(entities) = self.entities.get_mut(name) {
+            entities.retain(|&named| named != entity);
+            if entities.is_empty() {
+                self.entities.remove(name);
+            }
+        }
+        if !destroyed {
+            self.labels.remove(&entity.id);
+        }
+    }
+}
+
+impl Resource for Names {
+    fn resource_clone_fn() -> Option<fn(&Self) -> Self> {
+        Some(Self::clone)
+    }
+}
+
+/// An [`Entity`] along with its [`Name`], printed as `Entity(12v3 "PlayerShip")`, or the same as the [`Entity`] if it has no [`Name`]
+#[derive(Clone, PartialEq, Eq, Hash)]
+pub struct EntityLabel {
+    /// The [`Entity`] being labelled
+    pub entity: Entity,
+    /// The [`Name`] of the [`Entity`] if it has one
+    pub name: Option<Name>,
+}
+
+impl fmt::Debug for EntityLabel {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        fmt_label(f, self.entity, self.name.as_ref())
+    }
+}
+
+impl fmt::Display for EntityLabel {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        fmt_label(f, self.entity, self.name.as_ref())
+    }
+}
+
+pub(crate) fn fmt_label(
+    f: &mut fmt::Formatter<'_>,
+    entity: Entity,
+    name: Option<&Name>,
+) -> fmt::Result {
+    match name {
+        Some(name) => write!(
+            f,
+            "Entity({}v{} {:?})",
+            entity.id,
+            entity.version(),
+            name.as_str()
+        ),
+        None => write!(f, "{entity:?}"),
+    }
+}
+
+impl App {
+    /// Returns the [`Name`] of an [`Entity`] from [`Names`], destroyed [`Entity`]s keep their [`Name`] until their id is reused
+    pub fn entity_name(&self, entity: Entity) -> Option<Name> {
+        // the names are only used for diagnostics, so they are left out rather than waiting if a system is writing to them
+        let names = self.resources.get(&TypeId::of::<Names>())?.try_read()?;
+        names.downcast_ref::<Names>().resource.name(entity).cloned()
+    }
+
+    /// Returns an [`EntityLabel`] for printing an [`Entity`] along with its [`Name`]
+    pub fn entity_label(&self, entity: Entity) -> EntityLabel {
+        EntityLabel {
+            entity,
+            name: self.entity_name(entity),
+        }
+    }
+
+    pub(crate) fn register_names(&mut self) {
+        self.add_resource(Names::default());
+        self.name_sync = Some(Box::new(sync_names.into_system()));
+        self.component_hooks.insert(
+            TypeId::of::<Name>(),
+            ComponentHooks {
+                on_insert: Some(name_inserted),
+                on_remove: name_removed,
+                cloned: true,
+            },
+        );
+    }
+}
+
+/// Renames the [`Entity`]s whose [`Name`] was changed in place since the last sync
+fn sync_names(mut names: ResMut<'_, Names>, changed: Query<'_, Ref<'_, Name>>) {
+    if !changed.any_modified() {
+        return;
+    }
+    for (entity, name) in changed.including_disabled().iter() {
+        if !name.get_modified() || names.name(entity) == Some(&*name) {
+            continue;
+        }
+        if let Some(old) = names.name(entity).cloned() {
+            names.remove(entity, &old, false);
+        }
+        names.insert(entity, Name::clone(&name));
+    }
+}
+
+fn name_inserted(app: &mut App, entity: Entity) {
+    let Some(name) = app.component::<Name>(entity).cloned() else {
+        return;
+    };
+    if let Some(names) = app.resource_mut::<Names>() {
+        names.insert(entity, name);
+    }
+}
+
+fn name_removed(app: &mut App, entity: Entity) {
+    let Some(name) = app.component::<Name>(entity).cloned() else {
+        return;
+    };
+    let destroyed = !app.entity_exists(entity);
+    if let Some(names) = app.resource_mut::<Names>() {
+        names.remove(entity, &name, destroyed);
+    }
+}
@@ -0,0 +1,67 @@
+use crate::{
+    system::{merge_borrows, Borrow, SystemRunState},
+    App, IntoSystem, System,
+};
+
+/// A [`System`] that passes the output of one [`System`] into the [`In`](crate::In) parameter of another, created by [`IntoSystem::pipe`]
+pub struct PipeSystem<A, B>
+where
+    A: System,
+    B: System<Input = A::Output>,
+{
+    pub(crate) first: A,
+    pub(crate) second: B,
+}
+
+impl<A, B> System for PipeSystem<A, B>
+where
+    A: System,
+    B: System<Input = A::Output>,
+{
+    type Input = A::Input;
+    type Output = B::Output;
+
+    fn run(&mut self, input: Self::Input, state: &SystemRunState<'_>) -> Self::Output {
+        let output = self.first.run(input, state);
+        self.second.run(output, state)
+    }
+
+    fn apply_commands(&mut self, app: &mut App) {
+        self.first.apply_commands(app);
+        self.second.apply_commands(app);
+    }
+
+    fn get_resource_types(&self) -> impl Iterator<Item = Borrow> + '_
+    where
+        Self: Sized,
+    {
+        merge_borrows(
+            self.first
+                .get_resource_types()
+                .chain(self.second.get_resource_types()),
+        )
+    }
+
+    fn get_component_types(&self) -> impl Iterator<Item = Borrow> + '_
+    where
+        Self: Sized,
+    {
+        merge_borrows(
+            self.first
+                .get_component_types()
+                .chain(self.second.get_component_types()),
+        )
+    }
+}
+
+impl<A, B> IntoSystem<()> for PipeSystem<A, B>
+where
+    A: System,
+    B: System<Input = A::Output>,
+{
+    type System = Self;
+
+    fn into_system(self) -> Self::System {
+        self
+    }
+}
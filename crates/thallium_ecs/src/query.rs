@@ -3,9 +3,12 @@ use crate::{
     entities::EntityMap,
     query_parameters::OptionalComponentContainer,
     system::{Borrow, SystemRunState},
-    Component, Entity, QueryParameter, SystemParameter,
+    Component, Disabled, Entity, QueryParameter, SystemParameter,
+};
+use std::{
+    any::TypeId,
+    ops::{Deref, DerefMut},
 };
-use std::ops::{Deref, DerefMut};
 
 /// The [`QueryParameter`] for getting a reference to a [`Component`]
 pub struct Ref<'a, C>
@@ -86,6 +89,8 @@ where
 }
 
 /// A [`SystemParameter`] that lets you get references to [`Component`]s specified in `Q`
+///
+/// [`Disabled`] [`Entity`]s are skipped, unless `Q` includes [`Disabled`] itself or [`Query::including_disabled`] is used
 pub struct Query<'a, Q>
 where
     Q: QueryParameter,
@@ -94,6 +99,7 @@ where
     container: Q::ComponentContainer<'a>,
     current_tick: u64,
     last_run_tick: u64,
+    include_disabled: bool,
 }
 
 impl<'a, Q> SystemParameter for Query<'a, Q>
@@ -114,6 +120,8 @@ where
             container: Q::construct(state),
             current_tick: *current_tick,
             last_run_tick,
+            include_disabled: Q::get_component_types()
+                .any(|borrow| borrow.id == TypeId::of::<Disabled>()),
         }
     }
 
@@ -126,15 +134,49 @@ where
     }
 }
 
+impl<C> Query<'_, Ref<'_, C>>
+where
+    C: Component,
+{
+    /// Returns whether any [`Component`] could have been modified since the last run, which is only the case if something borrowed them mutably since then
+    pub(crate) fn any_modified(&self) -> bool {
+        self.container
+            .is_some_and(|container| self.last_run_tick < container.last_borrowed_mut_tick)
+    }
+}
+
 impl<'a, Q> Query<'a, Q>
 where
     Q: QueryParameter,
 {
+    /// Makes the [`Query`] include [`Disabled`] [`Entity`]s
+    pub fn including_disabled(self) -> Self {
+        Self {
+            include_disabled: true,
+            ..self
+        }
+    }
+
+    pub(crate) fn is_hidden(&self, entity: Entity) -> bool {
+        !self.include_disabled && self.entities.is_disabled(entity)
+    }
+
+    /// Same as [`Query::get`] but also gets the [`Component`]s of [`Disabled`] [`Entity`]s, for walking through them without returning them
+    pub(crate) fn get_including_disabled<'b>(
+        &'b self,
+        entity: Entity,
+    ) -> Option<<Q::ComponentContainer<'a> as ComponentContainerTrait<'a>>::Parameter<'b>> {
+        self.container.get(self.last_run_tick, entity)
+    }
+
     /// Gets access to the [`Component`]s that are attached to `entity`
     pub fn get<'b>(
         &'b self,
         entity: Entity,
     ) -> Option<<Q::ComponentContainer<'a> as ComponentContainerTrait<'a>>::Parameter<'b>> {
+        if self.is_hidden(entity) {
+            return None;
+        }
         self.container.get(self.last_run_tick, entity)
     }
 
@@ -143,6 +185,9 @@ where
         &'b mut self,
         entity: Entity,
     ) -> Option<<Q::ComponentContainer<'a> as ComponentContainerTrait<'a>>::ParameterMut<'b>> {
+        if self.is_hidden(entity) {
+            return None;
+        }
         self.container
             .get_mut(self.last_run_tick, self.current_tick, entity)
     }
@@ -153,6 +198,9 @@ where
         entities: [Entity; N],
     ) -> Option<[<Q::ComponentContainer<'a> as ComponentContainerTrait<'a>>::ParameterMut<'b>; N]>
     {
+        if entities.iter().any(|&entity| self.is_hidden(entity)) {
+            return None;
+        }
         self.container
             .get_many_mut(self.last_run_tick, self.current_tick, entities)
     }
@@ -167,7 +215,7 @@ where
         ),
     > + 'b {
         self.entities
-            .iter()
+            .iter(self.include_disabled)
             .zip(self.container.iter(self.last_run_tick))
             .filter_map(|(entity, parameter)| entity.zip(parameter))
     }
@@ -182,7 +230,7 @@ where
         ),
     > + 'b {
         self.entities
-            .iter()
+            .iter(self.include_disabled)
             .zip(
                 self.container
                     .iter_mut(self.last_run_tick, self.current_tick),
@@ -426,7 +474,11 @@ where
     }
 
     fn iter(&self, last_run_tick: u64) -> impl Iterator<Item = Option<Self::Parameter<'_>>> {
-        self.0.iter(last_run_tick).map(Some)
+        // the inner container may be shorter than the entity list, so it is padded with empty slots
+        self.0
+            .iter(last_run_tick)
+            .map(Some)
+            .chain(std::iter::repeat_with(|| Some(None)))
     }
 
     fn iter_mut(
@@ -434,7 +486,10 @@ where
         last_run_tick: u64,
         current_tick: u64,
     ) -> impl Iterator<Item = Option<Self::ParameterMut<'_>>> {
-        self.0.iter_mut(last_run_tick, current_tick).map(Some)
+        self.0
+            .iter_mut(last_run_tick, current_tick)
+            .map(Some)
+            .chain(std::iter::repeat_with(|| Some(None)))
     }
 }
 
@@ -74,7 +74,11 @@ where
                 .get(&TypeId::of::<C>())?
                 .try_write()
                 .expect("the lock should always be available"),
-            |components| components.downcast_mut::<C>(),
+            |components| {
+                let components = components.downcast_mut::<C>();
+                components.last_borrowed_mut_tick = state.current_tick;
+                components
+            },
         ))
     }
 
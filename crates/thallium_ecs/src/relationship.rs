@@ -0,0 +1,236 @@
+use crate::{
+    component_container::ComponentContainer, App, CommandError, CommandErrorKind, Commands,
+    Component, Entity, EntityCommands,
+};
+use parking_lot::RwLock;
+use std::any::TypeId;
+
+/// What happens to the other side of a [`Relationship`] when one side is destroyed
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Hash)]
+pub enum RelationshipCleanup {
+    /// The [`Relationship`] is removed from the sources when the target is destroyed
+    RemoveRelation,
+    /// The sources are destroyed along with the target
+    DespawnSource,
+    /// The target is destroyed when any of its sources are destroyed, and the [`Relationship`] is removed from the sources when the target is destroyed
+    DespawnTarget,
+}
+
+/// A [`Component`] on a source [`Entity`] that points at a target [`Entity`], the target is given a [`Relationship::Target`] [`Component`] listing all of its sources
+///
+/// The [`Relationship`] is kept in sync whenever it is added or removed, as long as it has been registered with [`App::register_relationship`] first, which [`App::relate`] does automatically
+pub trait Relationship: Component {
+    /// The [`Component`] that is added to the target
+    type Target: RelationshipTarget<Relationship = Self>;
+
+    /// What happens when either side of the [`Relationship`] is destroyed
+    const CLEANUP: RelationshipCleanup;
+
+    /// Constructs the [`Relationship`] pointing at `target`
+    fn from_target(target: Entity) -> Self;
+
+    /// Returns the target [`Entity`]
+    fn target(&self) -> Entity;
+}
+
+/// A [`Component`] on the target of a [`Relationship`] listing all of its sources, this should not be added or modified manually
+pub trait RelationshipTarget: Component {
+    /// The [`Relationship`] on the sources
+    type Relationship: Relationship<Target = Self>;
+
+    /// Constructs the [`RelationshipTarget`] from a list of sources
+    fn from_sources(sources: Vec<Entity>) -> Self;
+
+    /// Returns the sources in the order they were added
+    fn sources(&self) -> &[Entity];
+
+    /// Returns the sources mutably
+    fn sources_mut(&mut self) -> &mut Vec<Entity>;
+}
+
+/// Functions that are called when a [`Component`] is added to or removed from an [`Entity`]
+#[derive(Clone, Copy)]
+pub(crate) struct ComponentHooks {
+    /// Called after the [`Component`] has been added
+    pub(crate) on_insert: Option<fn(&mut App, Entity)>,
+    /// Called before the [`Component`] is removed, replaced, or its [`Entity`] destroyed, in which case the [`Entity`] no longer exists
+    pub(crate) on_remove: fn(&mut App, Entity),
+    /// Whether the [`Component`] is copied by [`App::clone_entity`], [`RelationshipTarget`]s are not because they are rebuilt by their [`Relationship`]s
+    pub(crate) cloned: bool,
+}
+
+impl App {
+    /// Registers a [`Relationship`] so that its [`Relationship::Target`] is kept in sync when it is added or removed
+    /// This does nothing if the [`Relationship`] is already registered
+    pub fn register_relationship<R>(&mut self)
+    where
+        R: Relationship,
+    {
+        self.component_hooks
+            .entry(TypeId::of::<R>())
+            .or_insert(ComponentHooks {
+                on_insert: Some(relationship_inserted::<R>),
+                on_remove: relationship_removed::<R>,
+                cloned: true,
+            });
+        self.component_hooks
+            .entry(TypeId::of::<R::Target>())
+            .or_insert(ComponentHooks {
+                on_insert: None,
+                on_remove: relationship_target_removed::<R>,
+                cloned: false,
+            });
+        // the target can always be rebuilt from its sources, so that it does not stop the whole app from being cloned
+        self.components
+            .entry(TypeId::of::<R::Target>())
+            .or_insert_with(|| RwLock::new(Box::new(ComponentContainer::<R::Target>::new())))
+            .get_mut()
+            .downcast_mut::<R::Target>()
+            .clone_fn
+            .get_or_insert(|target| R::Target::from_sources(target.sources().to_vec()));
+    }
+
+    /// Adds a [`Relationship`] from `source` to `target`, replacing any previous target
+    /// This reports a [`CommandError`] if either [`Entity`] does not exist
+    pub fn relate<R>(&mut self, source: Entity, target: Entity)
+    where
+        R: Relationship,
+    {
+        for entity in [source, target] {
+            if !self.entity_exists(entity) {
+                self.report_command_error(CommandError {
+                    kind: CommandErrorKind::Relate,
+                    entity,
+                    components: vec![std::any::type_name::<R>()],
+                });
+                return;
+            }
+        }
+
+        self.register_relationship::<R>();
+        self.insert_if_alive(source, R::from_target(target));
+    }
+
+    /// Removes the [`Relationship`] from `source`, returning its target
+    pub fn unrelate<R>(&mut self, source: Entity) -> Option<Entity>
+    where
+        R: Relationship,
+    {
+        self.remove_component::<R>(source)
+            .map(|relationship| relationship.target())
+    }
+}
+
+fn relationship_inserted<R>(app: &mut App, source: Entity)
+where
+    R: Relationship,
+{
+    let Some(target) = app.component::<R>(source).map(R::target) else {
+        return;
+    };
+
+    if !app.entity_exists(target) {
+        app.take_component::<R>(source);
+        app.report_command_error(CommandError {
+            kind: CommandErrorKind::Relate,
+            entity: target,
+            components: vec![std::any::type_name::<R>()],
+        });
+        return;
+    }
+
+    match app.component_mut::<R::Target>(target) {
+        Some(mut sources) => sources.sources_mut().push(source),
+        None => _ = app.insert_if_alive(target, R::Target::from_sources(vec![source])),
+    }
+}
+
+fn relationship_removed<R>(app: &mut App, source: Entity)
+where
+    R: Relationship,
+{
+    let Some(target) = app.component::<R>(source).map(R::target) else {
+        return;
+    };
+
+    let now_empty = app
+        .component_mut::<R::Target>(target)
+        .is_some_and(|mut sources| {
+            let sources = sources.sources_mut();
+            sources.retain(|&entity| entity != source);
+            sources.is_empty()
+        });
+    if now_empty {
+        app.remove_component::<R::Target>(target);
+    }
+
+    if R::CLEANUP == RelationshipCleanup::DespawnTarget && !app.entity_exists(source) {
+        app.destroy_entity(target);
+    }
+}
+
+fn relationship_target_removed<R>(app: &mut App, target: Entity)
+where
+    R: Relationship,
+{
+    let Some(sources) = app
+        .component::<R::Target>(target)
+        .map(|sources| sources.sources().to_vec())
+    else {
+        return;
+    };
+
+    let despawn = R::CLEANUP == RelationshipCleanup::DespawnSource && !app.entity_exists(target);
+    for source in sources {
+        // the relationship is taken without running its hooks, because the target is already being cleaned up
+        if app
+            .component::<R>(source)
+            .is_some_and(|relationship| relationship.target() == target)
+        {
+            app.take_component::<R>(source);
+            if despawn {
+                app.destroy_entity(source);
+            }
+        }
+    }
+}
+
+impl Commands<'_> {
+    /// Adds a [`Relationship`] from `source` to `target`, see [`App::relate`]
+    pub fn relate<R>(&mut self, source: Entity, target: Entity)
+    where
+        R: Relationship,
+    {
+        self.add(move |app: &mut App| app.relate::<R>(source, target));
+    }
+
+    /// Removes the [`Relationship`] from `source`, see [`App::unrelate`]
+    pub fn unrelate<R>(&mut self, source: Entity)
+    where
+        R: Relationship,
+    {
+        self.add(move |app: &mut App| _ = app.unrelate::<R>(source));
+    }
+}
+
+impl EntityCommands<'_, '_> {
+    /// Adds a [`Relationship`] from the [`Entity`] to `target`, see [`App::relate`]
+    pub fn relate<R>(&mut self, target: Entity) -> &mut Self
+    where
+        R: Relationship,
+    {
+        let source = self.id();
+        self.commands().relate::<R>(source, target);
+        self
+    }
+
+    /// Removes the [`Relationship`] from the [`Entity`], see [`App::unrelate`]
+    pub fn unrelate<R>(&mut self) -> &mut Self
+    where
+        R: Relationship,
+    {
+        let source = self.id();
+        self.commands().unrelate::<R>(source);
+        self
+    }
+}
@@ -12,7 +12,14 @@ use std::{
 };
 
 /// The trait implemented for all types that will be used as resources
-pub trait Resource: Sized + Send + Sync + 'static {}
+pub trait Resource: Sized + Send + Sync + 'static {
+    /// Returns the function used to clone this [`Resource`] by [`App::try_clone`](crate::App::try_clone), or [`None`] if it cannot be cloned
+    ///
+    /// `#[derive(Resource)]` implements this for types without generic parameters that implement [`Clone`], other types can be registered with [`App::register_resource_clone`](crate::App::register_resource_clone)
+    fn resource_clone_fn() -> Option<fn(&Self) -> Self> {
+        None
+    }
+}
 
 /// The [`SystemParameter`] for getting a reference to a [`Resource`]
 pub struct Res<'a, T>
@@ -60,7 +67,7 @@ where
                 .expect("Non-Option Res expects the resource to always exist")
                 .try_read()
                 .expect("the lock should always be available"),
-            |resource| resource.downcast_ref().unwrap(),
+            |resource| resource.downcast_ref(),
         )
     }
 
@@ -99,7 +106,7 @@ where
                 .get(&TypeId::of::<R>())?
                 .try_read()
                 .expect("the lock should always be available"),
-            |resource| resource.downcast_ref().unwrap(),
+            |resource| resource.downcast_ref(),
         ))
     }
 
@@ -188,7 +195,7 @@ where
                     .expect("Non-Option ResMut expects the resource to always exist")
                     .try_write()
                     .expect("the lock should always be available"),
-                |resource| resource.downcast_mut().unwrap(),
+                |resource| resource.downcast_mut(),
             ),
             state.current_tick,
         )
@@ -233,7 +240,7 @@ where
                     .get(&TypeId::of::<R>())?
                     .try_write()
                     .expect("the lock should always be available"),
-                |resource| resource.downcast_mut().unwrap(),
+                |resource| resource.downcast_mut(),
             ),
             state.current_tick,
         ))
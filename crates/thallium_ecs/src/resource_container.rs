@@ -1,4 +1,53 @@
 use crate::Resource;
+use std::any::Any;
+
+pub(crate) trait DynResourceContainer: Send + Sync {
+    fn as_any(&self) -> &dyn Any;
+    fn as_any_mut(&mut self) -> &mut dyn Any;
+    fn into_any(self: Box<Self>) -> Box<dyn Any>;
+    fn type_name(&self) -> &'static str;
+}
+
+impl<R> DynResourceContainer for ResourceContainer<R>
+where
+    R: Resource,
+{
+    fn as_any(&self) -> &dyn Any {
+        self
+    }
+
+    fn as_any_mut(&mut self) -> &mut dyn Any {
+        self
+    }
+
+    fn into_any(self: Box<Self>) -> Box<dyn Any> {
+        self
+    }
+
+    fn type_name(&self) -> &'static str {
+        std::any::type_name::<R>()
+    }
+}
+
+impl dyn DynResourceContainer + '_ {
+    pub fn downcast_ref<R>(&self) -> &ResourceContainer<R>
+    where
+        R: Resource,
+    {
+        self.as_any()
+            .downcast_ref::<ResourceContainer<R>>()
+            .unwrap()
+    }
+
+    pub fn downcast_mut<R>(&mut self) -> &mut ResourceContainer<R>
+    where
+        R: Resource,
+    {
+        self.as_any_mut()
+            .downcast_mut::<ResourceContainer<R>>()
+            .unwrap()
+    }
+}
 
 pub struct ResourceContainer<R>
 where
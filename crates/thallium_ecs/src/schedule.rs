@@ -0,0 +1,74 @@
+use std::{
+    any::{Any, TypeId},
+    fmt::Debug,
+    hash::{Hash, Hasher},
+};
+
+/// The trait implemented for all types that are used to name a schedule, see [`App::add_system`](crate::App::add_system)
+pub trait ScheduleLabel: Clone + Eq + Hash + Debug + Send + Sync + 'static {}
+
+/// The schedule that is run once by every call to [`App::update`](crate::App::update), after [`FixedUpdate`]
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Hash)]
+pub struct Update;
+
+impl ScheduleLabel for Update {}
+
+/// The schedule that is run zero or more times by every call to [`App::update`](crate::App::update), once for each step of `Time<Fixed>`, see [`Time`](crate::Time)
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Hash)]
+pub struct FixedUpdate;
+
+impl ScheduleLabel for FixedUpdate {}
+
+trait DynScheduleLabel: Debug + Send + Sync {
+    fn as_any(&self) -> &dyn Any;
+    fn dyn_eq(&self, other: &dyn DynScheduleLabel) -> bool;
+    fn dyn_hash(&self, state: &mut dyn Hasher);
+}
+
+impl<L> DynScheduleLabel for L
+where
+    L: ScheduleLabel,
+{
+    fn as_any(&self) -> &dyn Any {
+        self
+    }
+
+    fn dyn_eq(&self, other: &dyn DynScheduleLabel) -> bool {
+        other
+            .as_any()
+            .downcast_ref::<L>()
+            .is_some_and(|other| self == other)
+    }
+
+    fn dyn_hash(&self, mut state: &mut dyn Hasher) {
+        TypeId::of::<L>().hash(&mut state);
+        self.hash(&mut state);
+    }
+}
+
+/// A type erased [`ScheduleLabel`] that can be used as a key in a map
+#[derive(Debug)]
+pub(crate) struct ScheduleKey(Box<dyn DynScheduleLabel>);
+
+impl ScheduleKey {
+    pub(crate) fn new<L>(label: L) -> Self
+    where
+        L: ScheduleLabel,
+    {
+        Self(Box::new(label))
+    }
+}
+
+impl PartialEq for ScheduleKey {
+    fn eq(&self, other: &Self) -> bool {
+        self.0.dyn_eq(&*other.0)
+    }
+}
+
+impl Eq for ScheduleKey {}
+
+impl Hash for ScheduleKey {
+    fn hash<H: Hasher>(&self, state: &mut H) {
+        self.0.dyn_hash(state);
+    }
+}
@@ -0,0 +1,545 @@
+use crate::{
+    system::{Borrow, SystemRunState},
+    App, Component, IntoSystem, Query, Ref, Res, Resource, ScheduleLabel, System, SystemParameter,
+};
+use std::{
+    any::{Any, TypeId},
+    fmt::Debug,
+    hash::Hash,
+};
+
+/// The trait implemented for all types that are used as states, see [`App::insert_state`]
+pub trait States: Clone + Eq + Hash + Debug + Send + Sync + 'static {}
+
+/// A [`Resource`] holding the current value of the state `S`
+///
+/// This is changed by setting [`NextState`], the change is applied at the start of the next [`App::update`]
+#[derive(Debug, Clone)]
+pub struct State<S>(pub(crate) S)
+where
+    S: States;
+
+impl<S> State<S>
+where
+    S: States,
+{
+    /// Returns the current value of the state
+    pub fn get(&self) -> &S {
+        &self.0
+    }
+}
+
+impl<S> Resource for State<S>
+where
+    S: States,
+{
+    fn resource_clone_fn() -> Option<fn(&Self) -> Self> {
+        Some(Self::clone)
+    }
+}
+
+/// A [`Resource`] for queueing a change to the [`State`] `S`, the change is applied at the start of the next [`App::update`]
+#[derive(Debug, Clone)]
+pub struct NextState<S>(pub(crate) Option<S>)
+where
+    S: States;
+
+impl<S> NextState<S>
+where
+    S: States,
+{
+    /// Queues a change to `state`, replacing any change that was already queued
+    pub fn set(&mut self, state: S) {
+        self.0 = Some(state);
+    }
+
+    /// Cancels the queued change
+    pub fn reset(&mut self) {
+        self.0 = None;
+    }
+
+    /// Returns the queued change
+    pub fn pending(&self) -> Option<&S> {
+        self.0.as_ref()
+    }
+}
+
+impl<S> Default for NextState<S>
+where
+    S: States,
+{
+    fn default() -> Self {
+        Self(None)
+    }
+}
+
+impl<S> Resource for NextState<S>
+where
+    S: States,
+{
+    fn resource_clone_fn() -> Option<fn(&Self) -> Self> {
+        Some(Self::clone)
+    }
+}
+
+/// The schedule that is run when the [`State`] `S` changes to the contained value, and when the initial value is entered
+#[derive(Debug, Clone, PartialEq, Eq, Hash)]
+pub struct OnEnter<S>(pub S)
+where
+    S: States;
+
+impl<S> ScheduleLabel for OnEnter<S> where S: States {}
+
+/// The schedule that is run when the [`State`] `S` changes away from the contained value
+#[derive(Debug, Clone, PartialEq, Eq, Hash)]
+pub struct OnExit<S>(pub S)
+where
+    S: States;
+
+impl<S> ScheduleLabel for OnExit<S> where S: States {}
+
+/// The schedule that is run when the [`State`] `S` changes from `exited` to `entered`, after [`OnExit`] and before [`OnEnter`]
+#[derive(Debug, Clone, PartialEq, Eq, Hash)]
+pub struct OnTransition<S>
+where
+    S: States,
+{
+    /// The state that was exited
+    pub exited: S,
+    /// The state that was entered
+    pub entered: S,
+}
+
+impl<S> ScheduleLabel for OnTransition<S> where S: States {}
+
+/// A [`Component`] that makes its [`Entity`](crate::Entity) be destroyed when the [`State`] `S` exits the contained value
+#[derive(Debug, Clone)]
+pub struct StateScoped<S>(pub S)
+where
+    S: States;
+
+impl<S> Component for StateScoped<S>
+where
+    S: States,
+{
+    fn clone_fn() -> Option<fn(&Self) -> Self> {
+        Some(Self::clone)
+    }
+}
+
+/// Returns a condition for [`IntoSystem::run_if`] that is `true` while the [`State`] `S` is `state`
+pub fn in_state<S>(state: S) -> InState<S>
+where
+    S: States,
+{
+    InState(state)
+}
+
+/// A condition [`System`] that returns `true` while the [`State`] `S` is the contained value, created by [`in_state`]
+pub struct InState<S>(S)
+where
+    S: States;
+
+impl<S> System for InState<S>
+where
+    S: States,
+{
+    type Input = ();
+    type Output = bool;
+
+    fn run(&mut self, (): Self::Input, state: &SystemRunState<'_>) -> Self::Output {
+        let mut lock = <Option<Res<'_, State<S>>>>::lock(state);
+        <Option<Res<'_, State<S>>>>::construct(&mut lock, 0)
+            .is_some_and(|current| current.0 == self.0)
+    }
+
+    fn get_resource_types(&self) -> impl Iterator<Item = Borrow> + '_
+    where
+        Self: Sized,
+    {
+        <Option<Res<'_, State<S>>>>::get_resource_types()
+    }
+
+    fn get_component_types(&self) -> impl Iterator<Item = Borrow> + '_
+    where
+        Self: Sized,
+    {
+        std::iter::empty()
+    }
+}
+
+impl<S> IntoSystem<()> for InState<S>
+where
+    S: States,
+{
+    type System = Self;
+
+    fn into_system(self) -> Self::System {
+        self
+    }
+}
+
+/// A set of [`States`] that [`SubStates`] and [`ComputedStates`] are derived from, this is implemented for all [`States`] and tuples of [`States`]
+pub trait StateSet: Sized {
+    /// Gets the current values of the [`State`]s, returns [`None`] if any of them do not exist
+    fn current(app: &mut App) -> Option<Self>;
+}
+
+impl<S> StateSet for S
+where
+    S: States,
+{
+    fn current(app: &mut App) -> Option<Self> {
+        current_state::<S>(app)
+    }
+}
+
+macro_rules! state_set_tuple {
+    ($($param:ident),*) => {
+        impl<$($param),*> StateSet for ($($param,)*)
+        where
+            $($param: States,)*
+        {
+            fn current(app: &mut App) -> Option<Self> {
+                Some(($(current_state::<$param>(app)?,)*))
+            }
+        }
+    };
+}
+
+state_set_tuple!(A);
+state_set_tuple!(A, B);
+state_set_tuple!(A, B, C);
+state_set_tuple!(A, B, C, D);
+state_set_tuple!(A, B, C, D, E);
+state_set_tuple!(A, B, C, D, E, F);
+state_set_tuple!(A, B, C, D, E, F, G);
+state_set_tuple!(A, B, C, D, E, F, G, H);
+
+/// [`States`] that only exist while their source [`States`] have certain values, registered with [`App::add_sub_state`]
+///
+/// While it exists a sub state can be changed with [`NextState`] like any other [`State`]
+pub trait SubStates: States {
+    /// The [`States`] that decide whether this state exists
+    type SourceStates: StateSet;
+
+    /// Returns the value that this state should start with if it should exist for `sources`, or [`None`] if it should not exist
+    fn should_exist(sources: Self::SourceStates) -> Option<Self>;
+}
+
+/// [`States`] whose value is computed from other [`States`], registered with [`App::add_computed_state`]
+///
+/// A computed state can not be changed with [`NextState`], it is recomputed whenever its sources change
+pub trait ComputedStates: States {
+    /// The [`States`] that this state is computed from
+    type SourceStates: StateSet;
+
+    /// Computes the value of this state from `sources`, or [`None`] if it should not exist
+    fn compute(sources: Self::SourceStates) -> Option<Self>;
+}
+
+pub(crate) struct RegisteredState {
+    id: TypeId,
+    apply: fn(&mut App),
+    exit: fn(&mut App),
+    transition: fn(&mut App),
+    enter: fn(&mut App),
+    clone_pending: fn(&(dyn Any + Send + Sync)) -> Box<dyn Any + Send + Sync>,
+    /// The [`PendingTransition`] of the state, kept here rather than as a [`Resource`] so that it is never visible to systems
+    pending: Option<Box<dyn Any + Send + Sync>>,
+}
+
+impl Clone for RegisteredState {
+    fn clone(&self) -> Self {
+        Self {
+            pending: self.pending.as_deref().map(self.clone_pending),
+            ..*self
+        }
+    }
+}
+
+#[derive(Clone)]
+struct PendingTransition<S>
+where
+    S: States,
+{
+    exited: Option<S>,
+    entered: Option<S>,
+}
+
+fn clone_pending<S>(pending: &(dyn Any + Send + Sync)) -> Box<dyn Any + Send + Sync>
+where
+    S: States,
+{
+    Box::new(
+        pending
+            .downcast_ref::<PendingTransition<S>>()
+            .unwrap()
+            .clone(),
+    )
+}
+
+impl App {
+    /// Adds the [`State`] `S` with the initial value `state`, along with its [`NextState`], replacing the current value if it already exists
+    ///
+    /// [`OnEnter`] is run for the initial value at the start of the next [`App::update`]
+    pub fn insert_state<S>(&mut self, state: S)
+    where
+        S: States,
+    {
+        self.register_state::<S>(apply_next_state::<S>);
+        self.init_resource::<NextState<S>>();
+        let old = current_state::<S>(self);
+        set_state(self, Some(state.clone()));
+        record_transition(self, old, Some(state));
+    }
+
+    /// Adds the [`State`] `S` with its [`Default`] value if it does not already exist, see [`App::insert_state`]
+    pub fn init_state<S>(&mut self)
+    where
+        S: States + Default,
+    {
+        if !self.has_resource::<State<S>>() {
+            self.insert_state(S::default());
+        }
+    }
+
+    /// Registers the [`SubStates`] `S` along with its [`NextState`], its sources must already be registered
+    ///
+    /// The [`State`] `S` is added or removed at the start of each [`App::update`] depending on the current value of its sources
+    pub fn add_sub_state<S>(&mut self)
+    where
+        S: SubStates,
+    {
+        self.register_state::<S>(apply_sub_state::<S>);
+        self.init_resource::<NextState<S>>();
+    }
+
+    /// Registers the [`ComputedStates`] `S`, its sources must already be registered
+    ///
+    /// The [`State`] `S` is recomputed at the start of each [`App::update`] from the current value of its sources
+    pub fn add_computed_state<S>(&mut self)
+    where
+        S: ComputedStates,
+    {
+        self.register_state::<S>(apply_computed_state::<S>);
+    }
+
+    fn register_state<S>(&mut self, apply: fn(&mut App))
+    where
+        S: States,
+    {
+        if self
+            .state_transitions
+            .iter()
+            .any(|registered| registered.id == TypeId::of::<S>())
+        {
+            return;
+        }
+        self.state_transitions.push(RegisteredState {
+            id: TypeId::of::<S>(),
+            apply,
+            exit: exit_state::<S>,
+            transition: transition_state::<S>,
+            enter: enter_state::<S>,
+            clone_pending: clone_pending::<S>,
+            pending: None,
+        });
+    }
+
+    /// Applies all [`NextState`]s and recomputes all [`SubStates`] and [`ComputedStates`] in the order they were registered, so each state sees the new values of its sources
+    ///
+    /// Then [`OnExit`] is run for every state that changed in reverse registration order, then [`OnTransition`] and [`OnEnter`] in registration order
+    pub(crate) fn apply_state_transitions(&mut self) {
+        #[cfg(feature = "tracing")]
+        let _span = tracing::info_span!("state_transitions").entered();
+        let count = self.state_transitions.len();
+        for index in 0..count {
+            (self.state_transitions[index].apply)(self);
+        }
+        for index in (0..count).rev() {
+            (self.state_transitions[index].exit)(self);
+        }
+        for index in 0..count {
+            (self.state_transitions[index].transition)(self);
+        }
+        for index in 0..count {
+            (self.state_transitions[index].enter)(self);
+        }
+    }
+}
+
+fn current_state<S>(app: &mut App) -> Option<S>
+where
+    S: States,
+{
+    app.resource::<State<S>>().map(|state| state.0.clone())
+}
+
+fn set_state<S>(app: &mut App, state: Option<S>)
+where
+    S: States,
+{
+    match state {
+        Some(state) => match app.resource_mut::<State<S>>() {
+            Some(current) => current.0 = state,
+            None => app.add_resource(State(state)),
+        },
+        None => _ = app.remove_resource::<State<S>>(),
+    }
+}
+
+fn take_next_state<S>(app: &mut App) -> Option<S>
+where
+    S: States,
+{
+    if app
+        .resource::<NextState<S>>()
+        .is_none_or(|next| next.0.is_none())
+    {
+        return None;
+    }
+    app.resource_mut::<NextState<S>>()?.0.take()
+}
+
+fn registered_state<S>(app: &mut App) -> &mut RegisteredState
+where
+    S: States,
+{
+    app.state_transitions
+        .iter_mut()
+        .find(|registered| registered.id == TypeId::of::<S>())
+        .expect("states are registered before their transitions are recorded")
+}
+
+fn pending_transition<S>(app: &mut App) -> Option<&PendingTransition<S>>
+where
+    S: States,
+{
+    registered_state::<S>(app)
+        .pending
+        .as_deref()
+        .map(|pending| pending.downcast_ref().unwrap())
+}
+
+fn take_pending_transition<S>(app: &mut App) -> Option<PendingTransition<S>>
+where
+    S: States,
+{
+    registered_state::<S>(app)
+        .pending
+        .take()
+        .map(|pending| *pending.downcast().unwrap())
+}
+
+/// Records that `S` changed from `exited` to `entered`, merging with any transition that has not had its schedules run yet
+fn record_transition<S>(app: &mut App, exited: Option<S>, entered: Option<S>)
+where
+    S: States,
+{
+    let exited = match take_pending_transition::<S>(app) {
+        Some(pending) => pending.exited,
+        None => exited,
+    };
+    if exited != entered {
+        registered_state::<S>(app).pending = Some(Box::new(PendingTransition { exited, entered }));
+    }
+}
+
+fn apply_next_state<S>(app: &mut App)
+where
+    S: States,
+{
+    let Some(next) = take_next_state::<S>(app) else {
+        return;
+    };
+    let Some(current) = current_state::<S>(app) else {
+        return;
+    };
+    if next != current {
+        set_state(app, Some(next.clone()));
+        record_transition(app, Some(current), Some(next));
+    }
+}
+
+fn apply_sub_state<S>(app: &mut App)
+where
+    S: SubStates,
+{
+    let old = current_state::<S>(app);
+    let new = match S::SourceStates::current(app).and_then(S::should_exist) {
+        Some(initial) => match old.clone() {
+            Some(old) => Some(take_next_state::<S>(app).unwrap_or(old)),
+            None => Some(initial),
+        },
+        None => {
+            take_next_state::<S>(app);
+            None
+        }
+    };
+    if new != old {
+        set_state(app, new.clone());
+        record_transition(app, old, new);
+    }
+}
+
+fn apply_computed_state<S>(app: &mut App)
+where
+    S: ComputedStates,
+{
+    let old = current_state::<S>(app);
+    let new = S::SourceStates::current(app).and_then(S::compute);
+    if new != old {
+        set_state(app, new.clone());
+        record_transition(app, old, new);
+    }
+}
+
+fn exit_state<S>(app: &mut App)
+where
+    S: States,
+{
+    let Some(exited) = pending_transition::<S>(app).and_then(|pending| pending.exited.clone())
+    else {
+        return;
+    };
+
+    app.run_schedule(OnExit(exited.clone()));
+    let scoped = app.run(move |scoped: Query<'_, Ref<'_, StateScoped<S>>>| {
+        scoped
+            .including_disabled()
+            .iter()
+            .filter(|(_, scoped)| scoped.0 == exited)
+            .map(|(entity, _)| entity)
+            .collect::<Vec<_>>()
+    });
+    for entity in scoped {
+        app.destroy_entity(entity);
+    }
+}
+
+fn transition_state<S>(app: &mut App)
+where
+    S: States,
+{
+    let Some((exited, entered)) = pending_transition::<S>(app)
+        .and_then(|pending| Some((pending.exited.clone()?, pending.entered.clone()?)))
+    else {
+        return;
+    };
+    app.run_schedule(OnTransition { exited, entered });
+}
+
+fn enter_state<S>(app: &mut App)
+where
+    S: States,
+{
+    let Some(PendingTransition {
+        entered: Some(entered),
+        ..
+    }) = take_pending_transition::<S>(app)
+    else {
+        return;
+    };
+    app.run_schedule(OnEnter(entered));
+}
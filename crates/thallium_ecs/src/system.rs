@@ -1,24 +1,27 @@
 use crate::{
-    component_container::DynComponentContainer, entities::EntityMap, App, SystemParameter,
-};
-use parking_lot::RwLock;
-use std::{
-    any::{Any, TypeId},
-    collections::HashMap,
-    marker::PhantomData,
-    sync::mpsc::Sender,
+    command_queue::CommandQueue,
+    component_container::DynComponentContainer,
+    diagnostics::{measure, DiagnosticsCollector},
+    entities::EntityMap,
+    resource_container::DynResourceContainer,
+    App, Executor, PipeSystem, RunIf, SystemKey, SystemParameter,
 };
+use parking_lot::{Mutex, RwLock};
+use std::{any::TypeId, collections::HashMap, marker::PhantomData};
 
-pub(crate) type ResourceMap = HashMap<TypeId, RwLock<Box<dyn Any + Send + Sync>>>;
+pub(crate) type ResourceMap = HashMap<TypeId, RwLock<Box<dyn DynResourceContainer>>>;
 pub(crate) type ComponentMap = HashMap<TypeId, RwLock<Box<dyn DynComponentContainer>>>;
-pub(crate) type CommandSender = Sender<Box<dyn FnOnce(&mut App) + Send>>;
 
 #[derive(Clone, Copy)]
 pub struct SystemRunState<'a> {
     pub(crate) resources: &'a ResourceMap,
     pub(crate) entities: &'a EntityMap,
     pub(crate) components: &'a ComponentMap,
-    pub(crate) command_sender: &'a CommandSender,
+    pub(crate) commands: &'a Mutex<CommandQueue>,
+    pub(crate) diagnostics: Option<&'a DiagnosticsCollector>,
+    /// The [`SystemKey`] of the [`System`] that is being run, used to tell apart the timings of systems with the same name
+    pub(crate) system: Option<SystemKey>,
+    pub(crate) executor: &'a Executor,
     pub(crate) current_tick: u64,
 }
 
@@ -35,10 +38,43 @@ pub struct Borrow {
     pub borrow_type: BorrowType,
 }
 
+/// Merges [`Borrow`]s of the same type into one, a [`BorrowType::Mutable`] borrow takes priority over a [`BorrowType::Immutable`] one
+pub(crate) fn merge_borrows(borrows: impl Iterator<Item = Borrow>) -> impl Iterator<Item = Borrow> {
+    borrows
+        .fold(HashMap::new(), |mut a, borrow| {
+            if let (
+                Borrow {
+                    borrow_type: borrow_type @ BorrowType::Immutable,
+                    ..
+                },
+                BorrowType::Mutable,
+            ) = (a.entry(borrow.id).or_insert(borrow), borrow.borrow_type)
+            {
+                *borrow_type = BorrowType::Mutable
+            }
+            a
+        })
+        .into_values()
+}
+
+/// The parameter type that a [`SystemFunction`] uses to receive the input of a [`System`], this must be the first parameter of the function
+///
+/// This is usually used to receive the output of another [`System`] through [`IntoSystem::pipe`]
+pub struct In<T>(pub T);
+
 /// An ECS system that can be added to a [`SystemSet`](crate::SystemSet)
 pub trait System: Send + Sync {
+    /// The type passed into [`System::run`]
+    type Input;
+    /// The type returned from [`System::run`]
+    type Output;
+
     /// Runs the system
-    fn run(&mut self, state: &SystemRunState<'_>);
+    fn run(&mut self, input: Self::Input, state: &SystemRunState<'_>) -> Self::Output;
+    /// Applies all [`Commands`](crate::Commands) that were queued by this [`System`] when it was last run
+    fn apply_commands(&mut self, app: &mut App) {
+        _ = app;
+    }
     /// Returns an iterator over all [`Resource`](crate::Resource) types that this [`System`] will use
     fn get_resource_types(&self) -> impl Iterator<Item = Borrow> + '_
     where
@@ -49,6 +85,10 @@ pub trait System: Send + Sync {
         Self: Sized;
 }
 
+/// A handle to a [`System`] that has been registered with [`App::register_system`]
+#[derive(Debug, Clone, Copy, PartialEq, Eq, PartialOrd, Ord, Hash)]
+pub struct SystemId(pub(crate) u64);
+
 /// Trait for converting things into a [`System`]
 pub trait IntoSystem<Marker> {
     /// The type returned from [`IntoSystem::into_system`]
@@ -56,6 +96,36 @@ pub trait IntoSystem<Marker> {
 
     /// Converts `self` into a [`System`]
     fn into_system(self) -> Self::System;
+
+    /// Creates a [`System`] that runs `self` and then passes its output into the [`In`] parameter of `other`
+    fn pipe<Other, OtherMarker>(self, other: Other) -> PipeSystem<Self::System, Other::System>
+    where
+        Self: Sized,
+        Other: IntoSystem<OtherMarker>,
+        Other::System: System<Input = <Self::System as System>::Output>,
+    {
+        PipeSystem {
+            first: self.into_system(),
+            second: other.into_system(),
+        }
+    }
+
+    /// Creates a [`System`] that only runs `self` when `condition` returns `true`, the condition is checked every time the [`System`] is run
+    fn run_if<Condition, ConditionMarker>(
+        self,
+        condition: Condition,
+    ) -> RunIf<Self::System, Condition::System>
+    where
+        Self: Sized,
+        Self::System: System<Output = ()>,
+        Condition: IntoSystem<ConditionMarker>,
+        Condition::System: System<Input = (), Output = bool>,
+    {
+        RunIf {
+            system: self.into_system(),
+            condition: condition.into_system(),
+        }
+    }
 }
 
 pub struct SystemFunctionWrapper<F, Marker>
@@ -64,6 +134,7 @@ where
 {
     pub(crate) func: F,
     pub(crate) last_run_tick: u64,
+    pub(crate) commands: Mutex<CommandQueue>,
     pub(crate) marker: PhantomData<fn(Marker)>,
 }
 
@@ -71,9 +142,23 @@ impl<F, Marker> System for SystemFunctionWrapper<F, Marker>
 where
     F: SystemFunction<Marker>,
 {
-    fn run(&mut self, state: &SystemRunState<'_>) {
-        F::run(&mut self.func, state, self.last_run_tick);
+    type Input = F::Input;
+    type Output = F::Output;
+
+    fn run(&mut self, input: Self::Input, state: &SystemRunState<'_>) -> Self::Output {
+        #[cfg(feature = "tracing")]
+        let _span = tracing::info_span!("system", name = std::any::type_name::<F>()).entered();
+        let state = SystemRunState {
+            commands: &self.commands,
+            ..*state
+        };
+        let output = F::run(&mut self.func, input, &state, self.last_run_tick);
         self.last_run_tick = state.current_tick;
+        output
+    }
+
+    fn apply_commands(&mut self, app: &mut App) {
+        self.commands.get_mut().apply(app);
     }
 
     fn get_resource_types(&self) -> impl Iterator<Item = Borrow> + '_
@@ -101,6 +186,7 @@ where
         SystemFunctionWrapper {
             func: self,
             last_run_tick: 0,
+            commands: Mutex::new(CommandQueue::new()),
             marker: PhantomData,
         }
     }
@@ -108,8 +194,18 @@ where
 
 /// The trait for functions which can be used as [`System`]s
 pub trait SystemFunction<Marker>: Send + Sync {
+    /// The type of the [`In`] parameter of this function, or `()` if it doesnt have one
+    type Input;
+    /// The return type of this function
+    type Output;
+
     /// Runs the system
-    fn run(&mut self, state: &SystemRunState<'_>, last_run_tick: u64);
+    fn run(
+        &mut self,
+        input: Self::Input,
+        state: &SystemRunState<'_>,
+        last_run_tick: u64,
+    ) -> Self::Output;
     /// Gets the [`Resource`](crate::Component) types that this [`SystemFunction`] will use
     fn get_resource_types() -> impl Iterator<Item = Borrow>;
     /// Gets the [`Component`](crate::Component) types that this [`SystemFunction`] will use
@@ -118,19 +214,74 @@ pub trait SystemFunction<Marker>: Send + Sync {
 
 macro_rules! system_function_impl {
     ($($param:ident),*) => {
-        impl<Func, $($param),*> SystemFunction<fn($($param),*)> for Func
+        impl<Func, Out, $($param),*> SystemFunction<fn($($param),*) -> Out> for Func
         where
-            for<'a> Func: FnMut($($param),*) + FnMut($($param::This<'a>),*) + Send + Sync,
+            for<'a> Func: FnMut($($param),*) -> Out + FnMut($($param::This<'a>),*) -> Out + Send + Sync,
             $($param: SystemParameter,)*
         {
-            fn run(&mut self, state: &SystemRunState<'_>, last_run_tick: u64) {
+            type Input = ();
+            type Output = Out;
+
+            fn run(
+                &mut self,
+                input: Self::Input,
+                state: &SystemRunState<'_>,
+                last_run_tick: u64,
+            ) -> Self::Output {
+                _ = input;
+                _ = last_run_tick;
+                measure(
+                    state,
+                    std::any::type_name::<Func>(),
+                    || ($($param::lock(state),)*),
+                    |locks| {
+                        #[allow(non_snake_case)]
+                        let ($(mut $param,)*) = locks;
+                        self($($param::construct(&mut $param, last_run_tick)),*)
+                    },
+                )
+            }
+
+            fn get_resource_types() -> impl Iterator<Item = Borrow> {
+                std::iter::empty()
+                    $(
+                        .chain($param::get_resource_types())
+                    )*
+            }
+
+            fn get_component_types() -> impl Iterator<Item = Borrow> {
+                std::iter::empty()
+                    $(
+                        .chain($param::get_component_types())
+                    )*
+            }
+        }
+
+        impl<Func, Input, Out, $($param),*> SystemFunction<fn(In<Input>, $($param),*) -> Out> for Func
+        where
+            for<'a> Func: FnMut(In<Input>, $($param),*) -> Out + FnMut(In<Input>, $($param::This<'a>),*) -> Out + Send + Sync,
+            $($param: SystemParameter,)*
+        {
+            type Input = Input;
+            type Output = Out;
+
+            fn run(
+                &mut self,
+                input: Self::Input,
+                state: &SystemRunState<'_>,
+                last_run_tick: u64,
+            ) -> Self::Output {
                 _ = last_run_tick;
-                _ = state;
-                $(
-                    #[allow(non_snake_case)]
-                    let mut $param = $param::lock(state);
-                )*
-                self($($param::construct(&mut $param, last_run_tick)),*)
+                measure(
+                    state,
+                    std::any::type_name::<Func>(),
+                    || ($($param::lock(state),)*),
+                    |locks| {
+                        #[allow(non_snake_case)]
+                        let ($(mut $param,)*) = locks;
+                        self(In(input), $($param::construct(&mut $param, last_run_tick)),*)
+                    },
+                )
             }
 
             fn get_resource_types() -> impl Iterator<Item = Borrow> {
@@ -149,7 +300,6 @@ macro_rules! system_function_impl {
         }
     };
 }
-
 system_function_impl!();
 system_function_impl!(A);
 system_function_impl!(A, B);
@@ -1,38 +1,108 @@
 use crate::{
-    system::{Borrow, BorrowType, SystemRunState},
-    IntoSystem, System,
+    system::{merge_borrows, Borrow, BorrowType, SystemRunState},
+    App, IntoSystem, System, SystemKey,
 };
-use rayon::iter::{IntoParallelRefMutIterator, ParallelIterator};
-use std::{any::TypeId, collections::HashMap};
+use std::{
+    any::TypeId,
+    collections::HashMap,
+    sync::atomic::{AtomicU64, Ordering},
+};
+
+/// A handle to a [`System`] that has been registered with [`SystemSet::register_system`]
+///
+/// Handles are unique across all [`SystemSet`]s, so they can also be used to look up the timings of a [`System`] in [`SystemDiagnostics`](crate::SystemDiagnostics)
+#[derive(Debug, Clone, Copy, PartialEq, Eq, PartialOrd, Ord, Hash)]
+pub struct SystemHandle(u64);
+
+impl SystemHandle {
+    fn next() -> Self {
+        static NEXT_HANDLE: AtomicU64 = AtomicU64::new(0);
+        Self(NEXT_HANDLE.fetch_add(1, Ordering::Relaxed))
+    }
+}
+
+pub(crate) struct SystemEntry<'a> {
+    handle: SystemHandle,
+    enabled: bool,
+    resources: HashMap<TypeId, Borrow>,
+    components: HashMap<TypeId, Borrow>,
+    system: Box<dyn System<Input = (), Output = ()> + 'a>,
+}
+
+impl SystemEntry<'_> {
+    fn run(&mut self, state: &SystemRunState<'_>) {
+        if self.enabled {
+            let state = SystemRunState {
+                system: Some(SystemKey::Handle(self.handle)),
+                ..*state
+            };
+            self.system.run((), &state);
+        }
+    }
+}
 
 pub(crate) struct SystemGroup<'a> {
     resources: HashMap<TypeId, Borrow>,
     components: HashMap<TypeId, Borrow>,
-    systems: Vec<Box<dyn System + 'a>>,
+    systems: Vec<SystemEntry<'a>>,
 }
 
 /// A set of [`System`]s that can be run in parallel
-pub struct SystemSet<'a> {
+///
+/// The output of the [`SystemSet`] is the output of its output system, which runs after all other [`System`]s, see [`SystemSet::with_output`]
+pub struct SystemSet<'a, O = ()> {
     system_groups: Vec<SystemGroup<'a>>,
+    output_resources: HashMap<TypeId, Borrow>,
+    output_components: HashMap<TypeId, Borrow>,
+    output_system: Box<dyn System<Input = (), Output = O> + 'a>,
+    /// `false` for the empty output system added by [`SystemSet::new`], which is left out of the [`SystemDiagnostics`](crate::SystemDiagnostics)
+    measure_output: bool,
 }
 
 impl<'a> SystemSet<'a> {
     /// Constructs an empty [`SystemSet`]
     pub fn new() -> Self {
+        Self {
+            measure_output: false,
+            ..Self::with_output(|| {})
+        }
+    }
+}
+
+impl<'a, O> SystemSet<'a, O> {
+    /// Constructs an empty [`SystemSet`] with `output_system` being run after all other [`System`]s, its output is returned as the output of the [`SystemSet`]
+    pub fn with_output<S, Marker>(output_system: S) -> Self
+    where
+        S: IntoSystem<Marker>,
+        S::System: System<Input = (), Output = O> + 'a,
+    {
+        let output_system = output_system.into_system();
+        let (output_resources, output_components) = Self::check_system(&output_system);
         SystemSet {
             system_groups: Vec::new(),
+            output_resources,
+            output_components,
+            output_system: Box::new(output_system),
+            measure_output: true,
         }
     }
 
-    /// Registers a [`System`] with this [`SystemSet`]
-    pub fn register_system<S, Marker>(&mut self, system: S)
+    /// Registers a [`System`] with this [`SystemSet`], the returned [`SystemHandle`] can be used to disable or remove the [`System`] later
+    pub fn register_system<S, Marker>(&mut self, system: S) -> SystemHandle
     where
         S: IntoSystem<Marker>,
-        S::System: 'a,
+        S::System: System<Input = (), Output = ()> + 'a,
     {
         let system = system.into_system();
         let (resources, components) = Self::check_system(&system);
-        let system = Box::new(system);
+        let handle = SystemHandle::next();
+        let system = SystemEntry {
+            handle,
+            enabled: true,
+            resources: resources.clone(),
+            components: components.clone(),
+            system: Box::new(system),
+        };
         for system_group in &mut self.system_groups {
             if system_group.resources.iter().any(|(id, borrow)| {
                 let Some(other_borrow) = resources.get(id) else {
@@ -61,13 +131,76 @@ impl<'a> SystemSet<'a> {
             system_group.systems.push(system);
             system_group.resources.extend(resources);
             system_group.components.extend(components);
-            return;
+            return handle;
         }
         self.system_groups.push(SystemGroup {
             resources,
             components,
             systems: vec![system],
         });
+        handle
+    }
+
+    /// Enables or disables a [`System`] in this [`SystemSet`], returns `false` if `handle` does not refer to a [`System`] in this [`SystemSet`]
+    ///
+    /// A disabled [`System`] is not run, but it still keeps its borrows so that enabling it again can never conflict with other [`System`]s
+    pub fn set_enabled(&mut self, handle: SystemHandle, enabled: bool) -> bool {
+        let Some(system) = self
+            .system_groups
+            .iter_mut()
+            .flat_map(|system_group| &mut system_group.systems)
+            .find(|system| system.handle == handle)
+        else {
+            return false;
+        };
+        system.enabled = enabled;
+        true
+    }
+
+    /// Returns whether a [`System`] in this [`SystemSet`] is enabled, or [`None`] if `handle` does not refer to a [`System`] in this [`SystemSet`]
+    pub fn is_enabled(&self, handle: SystemHandle) -> Option<bool> {
+        self.system_groups
+            .iter()
+            .flat_map(|system_group| &system_group.systems)
+            .find(|system| system.handle == handle)
+            .map(|system| system.enabled)
+    }
+
+    /// Removes a [`System`] from this [`SystemSet`], returns `false` if `handle` does not refer to a [`System`] in this [`SystemSet`]
+    pub fn remove_system(&mut self, handle: SystemHandle) -> bool {
+        for (group_index, system_group) in self.system_groups.iter_mut().enumerate() {
+            let Some(index) = system_group
+                .systems
+                .iter()
+                .position(|system| system.handle == handle)
+            else {
+                continue;
+            };
+
+            system_group.systems.remove(index);
+            if system_group.systems.is_empty() {
+                self.system_groups.remove(group_index);
+            } else {
+                system_group.resources = merge_borrows(
+                    system_group
+                        .systems
+                        .iter()
+                        .flat_map(|system| system.resources.values().copied()),
+                )
+                .map(|borrow| (borrow.id, borrow))
+                .collect();
+                system_group.components = merge_borrows(
+                    system_group
+                        .systems
+                        .iter()
+                        .flat_map(|system| system.components.values().copied()),
+                )
+                .map(|borrow| (borrow.id, borrow))
+                .collect();
+            }
+            return true;
+        }
+        false
     }
 
     fn check_system<S>(system: &S) -> (HashMap<TypeId, Borrow>, HashMap<TypeId, Borrow>)
@@ -124,68 +257,110 @@ impl<'a> Default for SystemSet<'a> {
     }
 }
 
-impl<'a> System for SystemSet<'a> {
-    fn run(&mut self, state: &SystemRunState<'_>) {
-        for system_group in &mut self.system_groups {
-            system_group
-                .systems
-                .par_iter_mut()
-                .for_each(|system| system.run(state));
+fn run_system_group<T>(
+    state: &SystemRunState<'_>,
+    index: usize,
+    systems: &mut [T],
+    run: impl Fn(&mut T) + Send + Sync,
+) where
+    T: Send,
+{
+    #[cfg(feature = "tracing")]
+    let _span = tracing::info_span!("system_group", index, systems = systems.len()).entered();
+    _ = index;
+    state.executor.for_each(systems, run);
+}
+
+impl<'a, O> System for SystemSet<'a, O> {
+    type Input = ();
+    type Output = O;
+
+    fn run(&mut self, (): Self::Input, state: &SystemRunState<'_>) -> Self::Output {
+        if state.executor.is_single_threaded() {
+            // systems run in registration order, so a group is split wherever a system from another group was registered in between
+            let mut systems = self
+                .system_groups
+                .iter_mut()
+                .enumerate()
+                .flat_map(|(index, system_group)| {
+                    system_group
+                        .systems
+                        .iter_mut()
+                        .map(move |system| (index, system))
+                })
+                .collect::<Vec<_>>();
+            systems.sort_by_key(|(_, system)| system.handle);
+            for systems in systems.chunk_by_mut(|(a, _), (b, _)| a == b) {
+                run_system_group(state, systems[0].0, systems, |(_, system)| {
+                    system.run(state)
+                });
+            }
+        } else {
+            for (index, system_group) in self.system_groups.iter_mut().enumerate() {
+                run_system_group(state, index, &mut system_group.systems, |system| {
+                    system.run(state)
+                });
+            }
+        }
+        if self.measure_output {
+            self.output_system.run((), state)
+        } else {
+            let state = SystemRunState {
+                diagnostics: None,
+                ..*state
+            };
+            self.output_system.run((), &state)
         }
     }
 
+    fn apply_commands(&mut self, app: &mut App) {
+        let mut systems = self
+            .system_groups
+            .iter_mut()
+            .flat_map(|system_group| &mut system_group.systems)
+            .collect::<Vec<_>>();
+        systems.sort_by_key(|system| system.handle);
+        for system in systems {
+            system.system.apply_commands(app);
+        }
+        self.output_system.apply_commands(app);
+    }
+
     fn get_resource_types(&self) -> impl Iterator<Item = Borrow> + '_
     where
         Self: Sized,
     {
-        self.system_groups
-            .iter()
-            .fold(HashMap::new(), |mut a, b| {
-                for (&id, &borrow) in &b.resources {
-                    if let (
-                        Borrow {
-                            borrow_type: borrow_type @ BorrowType::Immutable,
-                            ..
-                        },
-                        BorrowType::Mutable,
-                    ) = (a.entry(id).or_insert(borrow), borrow.borrow_type)
-                    {
-                        *borrow_type = BorrowType::Mutable
-                    }
-                }
-                a
-            })
-            .into_values()
+        merge_borrows(
+            self.system_groups
+                .iter()
+                .flat_map(|system_group| system_group.resources.values().copied())
+                .chain(self.output_resources.values().copied()),
+        )
     }
 
     fn get_component_types(&self) -> impl Iterator<Item = Borrow> + '_
     where
         Self: Sized,
     {
-        self.system_groups
-            .iter()
-            .fold(HashMap::new(), |mut a, b| {
-                for (&id, &borrow) in &b.components {
-                    if let (
-                        Borrow {
-                            borrow_type: borrow_type @ BorrowType::Immutable,
-                            ..
-                        },
-                        BorrowType::Mutable,
-                    ) = (a.entry(id).or_insert(borrow), borrow.borrow_type)
-                    {
-                        *borrow_type = BorrowType::Mutable
-                    }
-                }
-                a
-            })
-            .into_values()
+        merge_borrows(
+            self.system_groups
+                .iter()
+                .flat_map(|system_group| system_group.components.values().copied())
+                .chain(self.output_components.values().copied()),
+        )
     }
 }
 
-impl<'a> System for &mut SystemSet<'a> {
-    fn run(&mut self, state: &SystemRunState<'_>) {
-        SystemSet::run(self, state)
+impl<'a, O> System for &mut SystemSet<'a, O> {
+    type Input = ();
+    type Output = O;
+
+    fn run(&mut self, input: Self::Input, state: &SystemRunState<'_>) -> Self::Output {
+        SystemSet::run(self, input, state)
+    }
+
+    fn apply_commands(&mut self, app: &mut App) {
+        SystemSet::apply_commands(self, app)
     }
 
     fn get_resource_types(&self) -> impl Iterator<Item = Borrow> + '_
@@ -203,7 +378,7 @@ impl<'a> System for &mut SystemSet<'a> {
     }
 }
 
-impl<'a> IntoSystem<()> for SystemSet<'a> {
+impl<'a, O> IntoSystem<()> for SystemSet<'a, O> {
     type System = Self;
 
     fn into_system(self) -> Self::System {
@@ -211,7 +386,7 @@ impl<'a> IntoSystem<()> for SystemSet<'a> {
     }
 }
 
-impl<'a> IntoSystem<()> for &mut SystemSet<'a> {
+impl<'a, O> IntoSystem<()> for &mut SystemSet<'a, O> {
     type System = Self;
 
     fn into_system(self) -> Self::System {
@@ -0,0 +1,325 @@
+use crate::Resource;
+use std::{
+    sync::{
+        atomic::{AtomicU64, Ordering},
+        Arc,
+    },
+    time::{Duration, Instant},
+};
+
+/// A source of time for [`App::update`](crate::App::update), set with [`App::set_clock`](crate::App::set_clock)
+pub trait Clock: Send + Sync + 'static {
+    /// Returns the time that has passed since some fixed point, this must never decrease
+    fn now(&self) -> Duration;
+}
+
+/// A [`Clock`] that reads the real time with [`Instant::now`], this is the default [`Clock`] of an [`App`](crate::App)
+pub struct SystemClock {
+    start: Instant,
+}
+
+impl SystemClock {
+    /// Constructs a [`SystemClock`] that starts at the current time
+    pub fn new() -> Self {
+        Self {
+            start: Instant::now(),
+        }
+    }
+}
+
+impl Default for SystemClock {
+    fn default() -> Self {
+        Self::new()
+    }
+}
+
+impl Clock for SystemClock {
+    fn now(&self) -> Duration {
+        self.start.elapsed()
+    }
+}
+
+/// A [`Clock`] that only advances when told to, so that tests can drive time deterministically
+///
+/// Clones of a [`ManualClock`] share the same time, so one can be given to the [`App`](crate::App) while another is kept to advance it
+#[derive(Debug, Clone, Default)]
+pub struct ManualClock {
+    nanos: Arc<AtomicU64>,
+}
+
+impl ManualClock {
+    /// Constructs a [`ManualClock`] that starts at zero
+    pub fn new() -> Self {
+        Self::default()
+    }
+
+    /// Moves the time of this [`ManualClock`] forward by `duration`
+    pub fn advance(&self, duration: Duration) {
+        self.nanos
+            .fetch_add(duration.as_nanos() as u64, Ordering::Relaxed);
+    }
+}
+
+impl Clock for ManualClock {
+    fn now(&self) -> Duration {
+        Duration::from_nanos(self.nanos.load(Ordering::Relaxed))
+    }
+}
+
+/// A [`Resource`] that tracks the time that has passed, advanced by [`App::update`](crate::App::update)
+///
+/// `Time<Real>`, `Time<Virtual>` and `Time<Fixed>` are separate [`Resource`]s for each kind of time, the plain [`Time`] [`Resource`] is a copy of `Time<Fixed>` while [`FixedUpdate`](crate::FixedUpdate) is running and a copy of `Time<Virtual>` otherwise
+#[derive(Debug, Clone, Copy, Default)]
+pub struct Time<T = ()> {
+    delta: Duration,
+    elapsed: Duration,
+    context: T,
+}
+
+impl<T> Time<T> {
+    /// Returns the time that passed between the last two updates
+    pub fn delta(&self) -> Duration {
+        self.delta
+    }
+
+    /// Returns [`Time::delta`] in seconds as an [`f32`]
+    pub fn delta_secs(&self) -> f32 {
+        self.delta.as_secs_f32()
+    }
+
+    /// Returns [`Time::delta`] in seconds as an [`f64`]
+    pub fn delta_secs_f64(&self) -> f64 {
+        self.delta.as_secs_f64()
+    }
+
+    /// Returns the total time that has passed
+    pub fn elapsed(&self) -> Duration {
+        self.elapsed
+    }
+
+    /// Returns [`Time::elapsed`] in seconds as an [`f32`], this loses precision as time goes on so prefer [`Time::elapsed_secs_f64`] for long running timing
+    pub fn elapsed_secs(&self) -> f32 {
+        self.elapsed.as_secs_f32()
+    }
+
+    /// Returns [`Time::elapsed`] in seconds as an [`f64`]
+    pub fn elapsed_secs_f64(&self) -> f64 {
+        self.elapsed.as_secs_f64()
+    }
+
+    /// Returns the data specific to the kind of time
+    pub fn context(&self) -> &T {
+        &self.context
+    }
+
+    /// Returns the data specific to the kind of time
+    pub fn context_mut(&mut self) -> &mut T {
+        &mut self.context
+    }
+
+    pub(crate) fn advance_by(&mut self, delta: Duration) {
+        self.delta = delta;
+        self.elapsed += delta;
+    }
+
+    pub(crate) fn as_generic(&self) -> Time {
+        Time {
+            delta: self.delta,
+            elapsed: self.elapsed,
+            context: (),
+        }
+    }
+}
+
+impl<T> Resource for Time<T> where T: Send + Sync + 'static {}
+
+/// The real time read from the [`Clock`], which is not affected by pausing or speed scaling
+#[derive(Debug, Clone, Copy, Default)]
+pub struct Real;
+
+/// The game time, which can be paused, sped up or slowed down, and is clamped so that long hitches do not cause huge jumps
+#[derive(Debug, Clone, Copy)]
+pub struct Virtual {
+    paused: bool,
+    relative_speed: f64,
+    max_delta: Duration,
+}
+
+impl Default for Virtual {
+    /// Returns an unpaused [`Virtual`] time running at normal speed, with a max delta of 250 milliseconds
+    fn default() -> Self {
+        Self {
+            paused: false,
+            relative_speed: 1.0,
+            max_delta: Duration::from_millis(250),
+        }
+    }
+}
+
+impl Time<Virtual> {
+    /// Pauses the virtual time, [`Time::delta`] will be zero until it is unpaused
+    pub fn pause(&mut self) {
+        self.context.paused = true;
+    }
+
+    /// Unpauses the virtual time
+    pub fn unpause(&mut self) {
+        self.context.paused = false;
+    }
+
+    /// Checks if the virtual time is paused
+    pub fn is_paused(&self) -> bool {
+        self.context.paused
+    }
+
+    /// Returns how fast the virtual time advances relative to real time
+    pub fn relative_speed(&self) -> f64 {
+        self.context.relative_speed
+    }
+
+    /// Sets how fast the virtual time advances relative to real time, `2.0` is double speed and `0.5` is half speed
+    ///
+    /// # Panics
+    /// Panics if `relative_speed` is negative or not finite
+    pub fn set_relative_speed(&mut self, relative_speed: f64) {
+        assert!(
+            relative_speed.is_finite() && relative_speed >= 0.0,
+            "the relative speed must be positive and finite"
+        );
+        self.context.relative_speed = relative_speed;
+    }
+
+    /// Returns the largest amount of real time that a single update can advance by, before speed scaling
+    pub fn max_delta(&self) -> Duration {
+        self.context.max_delta
+    }
+
+    /// Sets the largest amount of real time that a single update can advance by, before speed scaling
+    pub fn set_max_delta(&mut self, max_delta: Duration) {
+        self.context.max_delta = max_delta;
+    }
+
+    pub(crate) fn advance_virtual(&mut self, real_delta: Duration) {
+        let delta = if self.context.paused {
+            Duration::ZERO
+        } else {
+            real_delta
+                .min(self.context.max_delta)
+                .mul_f64(self.context.relative_speed)
+        };
+        self.advance_by(delta);
+    }
+}
+
+/// The time of the [`FixedUpdate`](crate::FixedUpdate) schedule, which advances in steps of [`Time::timestep`]
+///
+/// Virtual time is accumulated each update and spent in steps, the leftover time is exposed as [`Time::alpha`] for interpolating between steps
+#[derive(Debug, Clone, Copy)]
+pub struct Fixed {
+    timestep: Duration,
+    max_steps: u32,
+    overstep: Duration,
+    steps: u32,
+}
+
+impl Default for Fixed {
+    /// Returns a [`Fixed`] time that steps 60 times per second, running at most 5 steps per update
+    fn default() -> Self {
+        Self {
+            timestep: Duration::from_secs_f64(1.0 / 60.0),
+            max_steps: 5,
+            overstep: Duration::ZERO,
+            steps: 0,
+        }
+    }
+}
+
+impl Time<Fixed> {
+    /// Constructs a [`Time<Fixed>`] that steps every `timestep`, running at most 5 steps per update
+    ///
+    /// # Panics
+    /// Panics if `timestep` is zero
+    pub fn from_duration(timestep: Duration) -> Self {
+        let mut time = Self::default();
+        time.set_timestep(timestep);
+        time
+    }
+
+    /// Constructs a [`Time<Fixed>`] that steps `hz` times per second, running at most 5 steps per update
+    ///
+    /// # Panics
+    /// Panics if `hz` is not positive and finite
+    pub fn from_hz(hz: f64) -> Self {
+        assert!(
+            hz.is_finite() && hz > 0.0,
+            "the frequency must be positive and finite"
+        );
+        Self::from_duration(Duration::from_secs_f64(1.0 / hz))
+    }
+
+    /// Returns the amount of time that each step advances by
+    pub fn timestep(&self) -> Duration {
+        self.context.timestep
+    }
+
+    /// Sets the amount of time that each step advances by
+    ///
+    /// # Panics
+    /// Panics if `timestep` is zero
+    pub fn set_timestep(&mut self, timestep: Duration) {
+        assert!(!timestep.is_zero(), "the timestep must not be zero");
+        self.context.timestep = timestep;
+    }
+
+    /// Returns the most steps that are run in a single update, any whole steps past this are dropped to avoid falling further and further behind
+    pub fn max_steps(&self) -> u32 {
+        self.context.max_steps
+    }
+
+    /// Sets the most steps that are run in a single update
+    pub fn set_max_steps(&mut self, max_steps: u32) {
+        self.context.max_steps = max_steps;
+    }
+
+    /// Returns the number of steps that were run by the most recent update
+    pub fn steps(&self) -> u32 {
+        self.context.steps
+    }
+
+    /// Returns the time that has been accumulated but not yet spent on a step
+    pub fn overstep(&self) -> Duration {
+        self.context.overstep
+    }
+
+    /// Returns how far between the last step and the next step the virtual time is, from `0.0` to `1.0`
+    pub fn alpha(&self) -> f64 {
+        self.context.overstep.as_secs_f64() / self.context.timestep.as_secs_f64()
+    }
+
+    pub(crate) fn accumulate(&mut self, delta: Duration) {
+        self.context.overstep += delta;
+        self.context.steps = 0;
+    }
+
+    /// Spends the time for a single step if there is enough accumulated, otherwise drops any whole steps that were skipped because of [`Time::max_steps`]
+    pub(crate) fn expend(&mut self) -> bool {
+        let Fixed {
+            timestep,
+            max_steps,
+            overstep,
+            steps,
+        } = self.context;
+        if overstep < timestep {
+            return false;
+        }
+        if steps >= max_steps {
+            self.context.overstep =
+                Duration::from_nanos((overstep.as_nanos() % timestep.as_nanos()) as u64);
+            return false;
+        }
+        self.context.overstep -= timestep;
+        self.context.steps += 1;
+        self.advance_by(timestep);
+        true
+    }
+}
@@ -0,0 +1,272 @@
+use crate::{Component, Query, RefMut, Res, Resource, Time};
+use std::time::Duration;
+
+/// Whether a [`Timer`] stops when it finishes or starts again
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Hash, Default)]
+pub enum TimerMode {
+    /// The [`Timer`] stays finished once it has finished, until it is reset
+    #[default]
+    Once,
+    /// The [`Timer`] starts again each time it finishes, carrying over any extra time
+    Repeating,
+}
+
+/// Counts down a [`Duration`], usable as both a [`Component`] and a [`Resource`]
+///
+/// [`Timer`] [`Component`]s can be ticked automatically by adding [`tick_timers`] to a schedule
+#[derive(Debug, Clone, Default)]
+pub struct Timer {
+    duration: Duration,
+    elapsed: Duration,
+    mode: TimerMode,
+    paused: bool,
+    finished: bool,
+    times_finished_this_tick: u32,
+}
+
+impl Timer {
+    /// Constructs a [`Timer`] that finishes after `duration`
+    pub fn new(duration: Duration, mode: TimerMode) -> Self {
+        Self {
+            duration,
+            elapsed: Duration::ZERO,
+            mode,
+            paused: false,
+            finished: false,
+            times_finished_this_tick: 0,
+        }
+    }
+
+    /// Constructs a [`Timer`] that finishes after `seconds`
+    pub fn from_seconds(seconds: f32, mode: TimerMode) -> Self {
+        Self::new(Duration::from_secs_f32(seconds), mode)
+    }
+
+    /// Advances the [`Timer`] by `delta`, this does nothing while the [`Timer`] is paused
+    pub fn tick(&mut self, delta: Duration) -> &Self {
+        if self.paused {
+            self.times_finished_this_tick = 0;
+            if self.mode == TimerMode::Repeating {
+                self.finished = false;
+            }
+            return self;
+        }
+
+        match self.mode {
+            TimerMode::Once => {
+                let was_finished = self.finished;
+                self.elapsed = (self.elapsed + delta).min(self.duration);
+                self.finished = self.elapsed >= self.duration;
+                self.times_finished_this_tick = u32::from(self.finished && !was_finished);
+            }
+            TimerMode::Repeating => {
+                self.elapsed += delta;
+                if self.duration.is_zero() {
+                    self.elapsed = Duration::ZERO;
+                    self.times_finished_this_tick = 1;
+                } else {
+                    self.times_finished_this_tick =
+                        (self.elapsed.as_nanos() / self.duration.as_nanos()) as u32;
+                    self.elapsed = Duration::from_nanos(
+                        (self.elapsed.as_nanos() % self.duration.as_nanos()) as u64,
+                    );
+                }
+                self.finished = self.times_finished_this_tick > 0;
+            }
+        }
+        self
+    }
+
+    /// Returns the [`Duration`] that the [`Timer`] takes to finish
+    pub fn duration(&self) -> Duration {
+        self.duration
+    }
+
+    /// Sets the [`Duration`] that the [`Timer`] takes to finish
+    pub fn set_duration(&mut self, duration: Duration) {
+        self.duration = duration;
+    }
+
+    /// Returns the time that has passed since the [`Timer`] was started or last repeated
+    pub fn elapsed(&self) -> Duration {
+        self.elapsed
+    }
+
+    /// Sets the time that has passed, this does not change whether the [`Timer`] is finished until the next [`Timer::tick`]
+    pub fn set_elapsed(&mut self, elapsed: Duration) {
+        self.elapsed = elapsed;
+    }
+
+    /// Returns the time left until the [`Timer`] finishes
+    pub fn remaining(&self) -> Duration {
+        self.duration.saturating_sub(self.elapsed)
+    }
+
+    /// Returns how far through the [`Timer`] is, from `0.0` to `1.0`
+    pub fn fraction(&self) -> f32 {
+        if self.duration.is_zero() {
+            return 1.0;
+        }
+        self.elapsed.as_secs_f32() / self.duration.as_secs_f32()
+    }
+
+    /// Returns how much of the [`Timer`] is left, from `1.0` to `0.0`
+    pub fn fraction_remaining(&self) -> f32 {
+        1.0 - self.fraction()
+    }
+
+    /// Returns the [`TimerMode`] of the [`Timer`]
+    pub fn mode(&self) -> TimerMode {
+        self.mode
+    }
+
+    /// Sets the [`TimerMode`] of the [`Timer`]
+    pub fn set_mode(&mut self, mode: TimerMode) {
+        self.mode = mode;
+    }
+
+    /// Returns whether the [`Timer`] has finished, a [`TimerMode::Repeating`] [`Timer`] is only finished on the ticks that it finished during
+    pub fn finished(&self) -> bool {
+        self.finished
+    }
+
+    /// Returns whether the [`Timer`] finished during the last [`Timer::tick`]
+    pub fn just_finished(&self) -> bool {
+        self.times_finished_this_tick > 0
+    }
+
+    /// Returns the number of times the [`Timer`] finished during the last [`Timer::tick`], this can be more than 1 for a [`TimerMode::Repeating`] [`Timer`] that was ticked by more than its duration
+    pub fn times_finished_this_tick(&self) -> u32 {
+        self.times_finished_this_tick
+    }
+
+    /// Pauses the [`Timer`] so that [`Timer::tick`] does not advance it
+    pub fn pause(&mut self) {
+        self.paused = true;
+    }
+
+    /// Unpauses the [`Timer`]
+    pub fn unpause(&mut self) {
+        self.paused = false;
+    }
+
+    /// Checks if the [`Timer`] is paused
+    pub fn is_paused(&self) -> bool {
+        self.paused
+    }
+
+    /// Resets the [`Timer`] to the start, this does not change whether it is paused
+    pub fn reset(&mut self) {
+        self.elapsed = Duration::ZERO;
+        self.finished = false;
+        self.times_finished_this_tick = 0;
+    }
+}
+
+impl Component for Timer {
+    fn clone_fn() -> Option<fn(&Self) -> Self> {
+        Some(Self::clone)
+    }
+}
+
+impl Resource for Timer {
+    fn resource_clone_fn() -> Option<fn(&Self) -> Self> {
+        Some(Self::clone)
+    }
+}
+
+/// Counts up the time that has passed, usable as both a [`Component`] and a [`Resource`]
+///
+/// [`Stopwatch`] [`Component`]s can be ticked automatically by adding [`tick_stopwatches`] to a schedule
+#[derive(Debug, Clone, Default)]
+pub struct Stopwatch {
+    elapsed: Duration,
+    paused: bool,
+}
+
+impl Stopwatch {
+    /// Constructs a [`Stopwatch`] that starts at zero
+    pub fn new() -> Self {
+        Self::default()
+    }
+
+    /// Advances the [`Stopwatch`] by `delta`, this does nothing while the [`Stopwatch`] is paused
+    pub fn tick(&mut self, delta: Duration) -> &Self {
+        if !self.paused {
+            self.elapsed += delta;
+        }
+        self
+    }
+
+    /// Returns the time that has passed since the [`Stopwatch`] was started or reset
+    pub fn elapsed(&self) -> Duration {
+        self.elapsed
+    }
+
+    /// Returns [`Stopwatch::elapsed`] in seconds
+    pub fn elapsed_secs(&self) -> f32 {
+        self.elapsed.as_secs_f32()
+    }
+
+    /// Sets the time that has passed
+    pub fn set_elapsed(&mut self, elapsed: Duration) {
+        self.elapsed = elapsed;
+    }
+
+    /// Pauses the [`Stopwatch`] so that [`Stopwatch::tick`] does not advance it
+    pub fn pause(&mut self) {
+        self.paused = true;
+    }
+
+    /// Unpauses the [`Stopwatch`]
+    pub fn unpause(&mut self) {
+        self.paused = false;
+    }
+
+    /// Checks if the [`Stopwatch`] is paused
+    pub fn is_paused(&self) -> bool {
+        self.paused
+    }
+
+    /// Resets the [`Stopwatch`] to zero, this does not change whether it is paused
+    pub fn reset(&mut self) {
+        self.elapsed = Duration::ZERO;
+    }
+}
+
+impl Component for Stopwatch {
+    fn clone_fn() -> Option<fn(&Self) -> Self> {
+        Some(Self::clone)
+    }
+}
+
+impl Resource for Stopwatch {
+    fn resource_clone_fn() -> Option<fn(&Self) -> Self> {
+        Some(Self::clone)
+    }
+}
+
+/// A system that ticks all [`Timer`] [`Component`]s by the [`Time::delta`]
+///
+/// Paused [`Timer`]s are not touched once [`Timer::just_finished`] has been cleared, so they are not marked as modified
+/// The [`Timer`]s of [`Disabled`](crate::Disabled) [`Entity`](crate::Entity)s are not ticked, they carry on from where they were once the [`Entity`](crate::Entity) is enabled again
+pub fn tick_timers(time: Res<'_, Time>, mut timers: Query<'_, RefMut<'_, Timer>>) {
+    for (_, mut timer) in timers.iter_mut() {
+        if timer.is_paused() && !timer.just_finished() {
+            continue;
+        }
+        timer.tick(time.delta());
+    }
+}
+
+/// A system that ticks all [`Stopwatch`] [`Component`]s by the [`Time::delta`]
+///
+/// Paused [`Stopwatch`]es are not touched, so they are not marked as modified
+/// The [`Stopwatch`]es of [`Disabled`](crate::Disabled) [`Entity`](crate::Entity)s are not ticked, the same as [`tick_timers`]
+pub fn tick_stopwatches(time: Res<'_, Time>, mut stopwatches: Query<'_, RefMut<'_, Stopwatch>>) {
+    for (_, mut stopwatch) in stopwatches.iter_mut() {
+        if !stopwatch.is_paused() {
+            stopwatch.tick(time.delta());
+        }
+    }
+}
@@ -2,7 +2,7 @@
 
 /// An alias for [`thallium_ecs`], also with the related deives from [`thallium_derive`]
 pub mod ecs {
-    pub use thallium_derive::{Component, Resource};
+    pub use thallium_derive::{Component, Resource, ScheduleLabel, States};
     pub use thallium_ecs::*;
 }
 
@@ -10,3 +10,121 @@ pub mod ecs {
 pub mod derive {
     pub use thallium_derive::*;
 }
+
+/// An alias for [`glam`], the math library used by [`transform`]
+pub mod math {
+    pub use glam::*;
+}
+
+/// The [`Transform`](transform::Transform) and [`GlobalTransform`](transform::GlobalTransform) [`Component`](ecs::Component)s for positioning entities in the world
+pub mod transform;
+
+#[cfg(test)]
+mod tests {
+    use crate::{
+        ecs::{App, Component, Query, Ref, RefMut, Resource, Update},
+        math::{Vec3, Vec3Swizzles},
+        transform::{propagate_transforms, GlobalTransform, Transform},
+    };
+    use std::f32::consts::FRAC_PI_2;
+
+    #[test]
+    fn transform_propagation() {
+        let mut app = App::new();
+        app.add_system(Update, propagate_transforms);
+
+        let ship = app.create_entity();
+        app.add_component(ship, Transform::from_xyz(1.0, 0.0, 0.0));
+        app.add_component(ship, GlobalTransform::IDENTITY);
+        let turret = app.create_entity();
+        app.add_component(
+            turret,
+            Transform::from_xyz(0.0, 2.0, 0.0).with_rotation_2d(FRAC_PI_2),
+        );
+        app.add_component(turret, GlobalTransform::IDENTITY);
+        app.set_parent(turret, ship);
+        let barrel = app.create_entity();
+        app.add_component(barrel, Transform::from_xyz(3.0, 0.0, 0.0));
+        app.add_component(barrel, GlobalTransform::IDENTITY);
+        app.set_parent(barrel, turret);
+        let asteroid = app.create_entity();
+        app.add_component(asteroid, Transform::from_xyz(5.0, 5.0, 0.0));
+        app.add_component(asteroid, GlobalTransform::IDENTITY);
+
+        let translation = |app: &mut App, entity| {
+            app.run(
+                move |mut transforms: Query<'_, RefMut<'_, GlobalTransform>>| {
+                    transforms.get_mut(entity).unwrap().translation()
+                },
+            )
+        };
+
+        app.update();
+        assert_eq!(translation(&mut app, ship), Vec3::new(1.0, 0.0, 0.0));
+        assert_eq!(translation(&mut app, turret), Vec3::new(1.0, 2.0, 0.0));
+        assert!(translation(&mut app, barrel)
+            .xy()
+            .abs_diff_eq((1.0, 5.0).into(), 1e-5));
+        assert_eq!(translation(&mut app, asteroid), Vec3::new(5.0, 5.0, 0.0));
+
+        // the components were added outside of a system, so the next update still sees them as modified
+        app.update();
+
+        // writing to a GlobalTransform does not get it recomputed, so the asteroid shows which subtrees are
+        app.run(
+            move |mut transforms: Query<'_, RefMut<'_, GlobalTransform>>| {
+                *transforms.get_mut(asteroid).unwrap() = GlobalTransform::IDENTITY;
+            },
+        );
+        app.run(move |mut transforms: Query<'_, RefMut<'_, Transform>>| {
+            transforms.get_mut(ship).unwrap().translate(Vec3::Y);
+        });
+        app.update();
+        assert_eq!(translation(&mut app, ship), Vec3::new(1.0, 1.0, 0.0));
+        assert!(translation(&mut app, barrel)
+            .xy()
+            .abs_diff_eq((1.0, 6.0).into(), 1e-5));
+        assert_eq!(translation(&mut app, asteroid), Vec3::ZERO);
+
+        // reparenting also marks the subtree as changed
+        app.set_parent(barrel, ship);
+        app.update();
+        assert_eq!(translation(&mut app, barrel), Vec3::new(4.0, 1.0, 0.0));
+
+        // removing the parent makes the barrel a root again
+        app.remove_parent(barrel);
+        app.update();
+        let barrel_transform = app.run(
+            move |transforms: Query<'_, (Ref<'_, Transform>, Ref<'_, GlobalTransform>)>| {
+                let (transform, global_transform) = transforms.get(barrel).unwrap();
+                (*transform, *global_transform)
+            },
+        );
+        assert_eq!(
+            barrel_transform.1,
+            GlobalTransform::from(barrel_transform.0)
+        );
+    }
+
+    #[test]
+    fn derived_clone_fn() {
+        #[derive(Component)]
+        struct Handle;
+        #[derive(Clone, Resource)]
+        struct Score;
+
+        assert!(Transform::clone_fn().is_some());
+        assert!(Handle::clone_fn().is_none());
+        assert!(Score::resource_clone_fn().is_some());
+
+        let mut app = App::new();
+        let ship = app.create_entity();
+        app.add_component(ship, Transform::from_xyz(1.0, 2.0, 3.0));
+        app.add_component(ship, Handle);
+        let clone = app.clone_entity(ship);
+        let translation = app.run(move |mut transforms: Query<'_, RefMut<'_, Transform>>| {
+            transforms.get_mut(clone).unwrap().translation
+        });
+        assert_eq!(translation, Vec3::new(1.0, 2.0, 3.0));
+    }
+}
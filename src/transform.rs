@@ -0,0 +1,290 @@
+use crate::{
+    ecs::{Children, Commands, Component, Parent, Query, Ref, RefMut},
+    math::{Affine3A, Mat4, Quat, Vec2, Vec3},
+};
+use std::ops::Mul;
+
+/// The position, rotation, and scale of an [`Entity`](crate::ecs::Entity) relative to its [`Parent`], or to the world if it has no [`Parent`]
+///
+/// The 2D helpers treat the xy plane as the world, with rotations around the z axis
+#[derive(Debug, Clone, Copy, PartialEq, Component)]
+pub struct Transform {
+    /// The position of the [`Entity`](crate::ecs::Entity)
+    pub translation: Vec3,
+    /// The rotation of the [`Entity`](crate::ecs::Entity)
+    pub rotation: Quat,
+    /// The scale of the [`Entity`](crate::ecs::Entity)
+    pub scale: Vec3,
+}
+
+impl Transform {
+    /// A [`Transform`] that does nothing
+    pub const IDENTITY: Self = Self {
+        translation: Vec3::ZERO,
+        rotation: Quat::IDENTITY,
+        scale: Vec3::ONE,
+    };
+
+    /// Constructs a [`Transform`] at `translation`
+    pub const fn from_translation(translation: Vec3) -> Self {
+        Self {
+            translation,
+            ..Self::IDENTITY
+        }
+    }
+
+    /// Constructs a [`Transform`] at the position `(x, y, z)`
+    pub const fn from_xyz(x: f32, y: f32, z: f32) -> Self {
+        Self::from_translation(Vec3::new(x, y, z))
+    }
+
+    /// Constructs a [`Transform`] at the 2D position `translation`
+    pub const fn from_translation_2d(translation: Vec2) -> Self {
+        Self::from_translation(Vec3::new(translation.x, translation.y, 0.0))
+    }
+
+    /// Constructs a [`Transform`] with `rotation`
+    pub const fn from_rotation(rotation: Quat) -> Self {
+        Self {
+            rotation,
+            ..Self::IDENTITY
+        }
+    }
+
+    /// Constructs a [`Transform`] rotated by `angle` radians counter-clockwise in 2D
+    pub fn from_rotation_2d(angle: f32) -> Self {
+        Self::from_rotation(Quat::from_rotation_z(angle))
+    }
+
+    /// Constructs a [`Transform`] with `scale`
+    pub const fn from_scale(scale: Vec3) -> Self {
+        Self {
+            scale,
+            ..Self::IDENTITY
+        }
+    }
+
+    /// Returns the [`Transform`] with its translation replaced by `translation`
+    pub const fn with_translation(self, translation: Vec3) -> Self {
+        Self {
+            translation,
+            ..self
+        }
+    }
+
+    /// Returns the [`Transform`] with its rotation replaced by `rotation`
+    pub const fn with_rotation(self, rotation: Quat) -> Self {
+        Self { rotation, ..self }
+    }
+
+    /// Returns the [`Transform`] with its rotation replaced by a 2D rotation of `angle` radians
+    pub fn with_rotation_2d(self, angle: f32) -> Self {
+        self.with_rotation(Quat::from_rotation_z(angle))
+    }
+
+    /// Returns the [`Transform`] with its scale replaced by `scale`
+    pub const fn with_scale(self, scale: Vec3) -> Self {
+        Self { scale, ..self }
+    }
+
+    /// Returns the translation in the xy plane
+    pub fn translation_2d(&self) -> Vec2 {
+        self.translation.truncate()
+    }
+
+    /// Returns the rotation around the z axis in radians, ignoring any other rotation
+    pub fn rotation_2d(&self) -> f32 {
+        let forward = self.rotation * Vec3::X;
+        forward.y.atan2(forward.x)
+    }
+
+    /// Moves the [`Transform`] by `offset`
+    pub fn translate(&mut self, offset: Vec3) {
+        self.translation += offset;
+    }
+
+    /// Rotates the [`Transform`] by `rotation`
+    pub fn rotate(&mut self, rotation: Quat) {
+        self.rotation = rotation * self.rotation;
+    }
+
+    /// Rotates the [`Transform`] by `angle` radians counter-clockwise in 2D
+    pub fn rotate_2d(&mut self, angle: f32) {
+        self.rotate(Quat::from_rotation_z(angle));
+    }
+
+    /// Returns the [`Transform`] as an affine matrix
+    pub fn compute_affine(&self) -> Affine3A {
+        Affine3A::from_scale_rotation_translation(self.scale, self.rotation, self.translation)
+    }
+
+    /// Returns the [`Transform`] as a 4x4 matrix
+    pub fn compute_matrix(&self) -> Mat4 {
+        Mat4::from_scale_rotation_translation(self.scale, self.rotation, self.translation)
+    }
+
+    /// Transforms `point` from the local space of the [`Transform`] into the space of its parent
+    pub fn transform_point(&self, point: Vec3) -> Vec3 {
+        self.translation + self.rotation * (self.scale * point)
+    }
+}
+
+impl Default for Transform {
+    fn default() -> Self {
+        Self::IDENTITY
+    }
+}
+
+/// The position, rotation, and scale of an [`Entity`](crate::ecs::Entity) relative to the world, this is computed from the [`Transform`]s of the [`Entity`](crate::ecs::Entity) and its ancestors by [`propagate_transforms`]
+#[derive(Debug, Clone, Copy, PartialEq, Component)]
+pub struct GlobalTransform(Affine3A);
+
+impl GlobalTransform {
+    /// A [`GlobalTransform`] that does nothing
+    pub const IDENTITY: Self = Self(Affine3A::IDENTITY);
+
+    /// Returns the [`GlobalTransform`] as an affine matrix
+    pub fn affine(&self) -> Affine3A {
+        self.0
+    }
+
+    /// Returns the [`GlobalTransform`] as a 4x4 matrix
+    pub fn compute_matrix(&self) -> Mat4 {
+        Mat4::from(self.0)
+    }
+
+    /// Returns the position in the world
+    pub fn translation(&self) -> Vec3 {
+        self.0.translation.into()
+    }
+
+    /// Returns the position in the world in the xy plane
+    pub fn translation_2d(&self) -> Vec2 {
+        self.translation().truncate()
+    }
+
+    /// Decomposes the [`GlobalTransform`] into a [`Transform`], this is lossy if any ancestor has a non-uniform scale and a rotation
+    pub fn compute_transform(&self) -> Transform {
+        let (scale, rotation, translation) = self.0.to_scale_rotation_translation();
+        Transform {
+            translation,
+            rotation,
+            scale,
+        }
+    }
+
+    /// Transforms `point` from the local space of the [`Entity`](crate::ecs::Entity) into world space
+    pub fn transform_point(&self, point: Vec3) -> Vec3 {
+        self.0.transform_point3(point)
+    }
+
+    /// Returns the [`GlobalTransform`] of a child with the local `transform`
+    pub fn mul_transform(&self, transform: Transform) -> Self {
+        Self(self.0 * transform.compute_affine())
+    }
+}
+
+impl Default for GlobalTransform {
+    fn default() -> Self {
+        Self::IDENTITY
+    }
+}
+
+impl From<Transform> for GlobalTransform {
+    fn from(transform: Transform) -> Self {
+        Self(transform.compute_affine())
+    }
+}
+
+impl Mul<Transform> for GlobalTransform {
+    type Output = Self;
+
+    fn mul(self, transform: Transform) -> Self::Output {
+        self.mul_transform(transform)
+    }
+}
+
+impl Mul for GlobalTransform {
+    type Output = Self;
+
+    fn mul(self, global_transform: Self) -> Self::Output {
+        Self(self.0 * global_transform.0)
+    }
+}
+
+/// Marks an [`Entity`](crate::ecs::Entity) that had a [`Parent`] the last time [`propagate_transforms`] ran, so that losing the [`Parent`] is noticed
+///
+/// This is added and removed by [`propagate_transforms`] and does not need to be added manually
+#[derive(Debug, Clone, Copy, Component)]
+pub struct WasParented;
+
+/// A system that updates the [`GlobalTransform`] of every [`Entity`](crate::ecs::Entity) that has both a [`Transform`] and a [`GlobalTransform`]
+///
+/// Only subtrees of the hierarchy where a [`Transform`] or [`Parent`] was modified or a [`Parent`] was removed since the last run are recomputed,
+/// an [`Entity`](crate::ecs::Entity) whose parent has no [`Transform`] is treated as a root, and its children without a [`Transform`] stop the propagation
+///
+/// [`Disabled`](crate::ecs::Disabled) [`Entity`](crate::ecs::Entity)s are updated as well, so that they are in the right place when they are enabled again
+pub fn propagate_transforms(
+    transforms: Query<
+        '_,
+        (
+            Ref<'_, Transform>,
+            RefMut<'_, GlobalTransform>,
+            Option<Ref<'_, Parent>>,
+            Option<Ref<'_, WasParented>>,
+        ),
+    >,
+    children: Query<'_, Ref<'_, Children>>,
+    mut commands: Commands<'_>,
+) {
+    let mut transforms = transforms.including_disabled();
+    let children = children.including_disabled();
+    for (entity, (_, _, parent, was_parented)) in transforms.iter() {
+        match (parent.is_some(), was_parented.is_some()) {
+            (true, false) => commands.try_add_components(entity, WasParented),
+            (false, true) => commands.try_remove_components::<WasParented>(entity),
+            _ => {}
+        }
+    }
+
+    let roots = transforms
+        .iter()
+        .filter(|(_, (_, _, parent, _))| {
+            parent
+                .as_ref()
+                .is_none_or(|parent| transforms.get(parent.get()).is_none())
+        })
+        .map(|(entity, _)| entity)
+        .collect::<Vec<_>>();
+
+    let mut stack = Vec::new();
+    for root in roots {
+        stack.push((root, GlobalTransform::IDENTITY, false));
+        while let Some((entity, parent_global_transform, parent_changed)) = stack.pop() {
+            let Some((transform, mut global_transform, parent, was_parented)) =
+                transforms.get_mut(entity)
+            else {
+                continue;
+            };
+
+            let changed = parent_changed
+                || transform.get_modified()
+                || match parent {
+                    Some(parent) => parent.get_modified(),
+                    None => was_parented.is_some(),
+                };
+            if changed {
+                *global_transform = parent_global_transform * *transform;
+            }
+
+            let global_transform = *global_transform;
+            if let Some(children) = children.get(entity) {
+                stack.extend(
+                    children
+                        .iter()
+                        .map(|&child| (child, global_transform, changed)),
+                );
+            }
+        }
+    }
+}